@@ -0,0 +1,332 @@
+//! Actions that change system state: terminating processes and resetting
+//! single TCP connections. Every attempt, successful or not, is meant to be
+//! written to the `AuditLog`.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ActionError {
+    /// The OS refused, usually because we are not elevated / lack
+    /// `CAP_NET_ADMIN`.
+    PermissionDenied(String),
+    NotFound(String),
+    Unsupported(String),
+    InvalidTarget(String),
+    Failed(String),
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::PermissionDenied(msg) => write!(
+                f,
+                "permission denied: {} (try running net-process as administrator/root)",
+                msg
+            ),
+            ActionError::NotFound(msg) => write!(f, "not found: {}", msg),
+            ActionError::Unsupported(msg) => write!(f, "not supported: {}", msg),
+            ActionError::InvalidTarget(msg) => write!(f, "invalid target: {}", msg),
+            ActionError::Failed(msg) => write!(f, "failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+impl ActionError {
+    fn from_io(err: io::Error, target: &str) -> Self {
+        let msg = format!("{} ({})", target, err);
+        match err.kind() {
+            io::ErrorKind::PermissionDenied => ActionError::PermissionDenied(msg),
+            io::ErrorKind::NotFound => ActionError::NotFound(msg),
+            io::ErrorKind::Unsupported => ActionError::Unsupported(msg),
+            _ => match err.raw_os_error() {
+                #[cfg(unix)]
+                Some(libc::ESRCH) => ActionError::NotFound(msg),
+                #[cfg(unix)]
+                Some(libc::EOPNOTSUPP) => ActionError::Unsupported(msg),
+                #[cfg(windows)]
+                Some(87) => ActionError::NotFound(msg), // ERROR_INVALID_PARAMETER
+                _ => ActionError::Failed(msg),
+            },
+        }
+    }
+}
+
+/// Asks a process to exit and kills it if it is still around after `grace`.
+/// On Windows there is no polite variant, so this is `TerminateProcess`.
+#[cfg(unix)]
+pub fn terminate_process(pid: u32, grace: Duration) -> Result<(), ActionError> {
+    let target = format!("process {}", pid);
+    let pid = libc::pid_t::try_from(pid)
+        .ok()
+        .filter(|pid| *pid > 0)
+        .ok_or_else(|| ActionError::InvalidTarget(target.clone()))?;
+
+    let signal = |signal| {
+        // SAFETY: kill(2) has no memory safety requirements.
+        if unsafe { libc::kill(pid, signal) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    };
+
+    signal(libc::SIGTERM).map_err(|err| ActionError::from_io(err, &target))?;
+
+    let step = Duration::from_millis(50);
+    let mut waited = Duration::ZERO;
+    while waited < grace {
+        std::thread::sleep(step);
+        waited += step;
+        if signal(0).is_err() {
+            return Ok(());
+        }
+    }
+
+    match signal(libc::SIGKILL) {
+        Err(err) if err.raw_os_error() == Some(libc::ESRCH) => Ok(()),
+        result => result.map_err(|err| ActionError::from_io(err, &target)),
+    }
+}
+
+#[cfg(windows)]
+pub fn terminate_process(pid: u32, _grace: Duration) -> Result<(), ActionError> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+    let target = format!("process {}", pid);
+
+    // SAFETY: plain Win32 calls, the handle is closed before returning.
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if handle == 0 {
+            return Err(ActionError::from_io(io::Error::last_os_error(), &target));
+        }
+        let terminated = TerminateProcess(handle, 1);
+        let err = io::Error::last_os_error();
+        CloseHandle(handle);
+        if terminated == 0 {
+            return Err(ActionError::from_io(err, &target));
+        }
+    }
+    Ok(())
+}
+
+/// Resets a single TCP connection. Uses `SOCK_DESTROY` on Linux (needs
+/// `CAP_NET_ADMIN` and `CONFIG_INET_DIAG_DESTROY`) and `SetTcpEntry` on
+/// Windows, which only handles IPv4.
+pub fn close_connection(local: SocketAddr, remote: SocketAddr) -> Result<(), ActionError> {
+    let target = format!("connection {} -> {}", local, remote);
+    close_tcp(local, remote, &target)
+}
+
+#[cfg(target_os = "linux")]
+fn close_tcp(local: SocketAddr, remote: SocketAddr, target: &str) -> Result<(), ActionError> {
+    crate::sock_diag::destroy_tcp_socket(local, remote)
+        .map_err(|err| ActionError::from_io(err, target))
+}
+
+#[cfg(windows)]
+fn close_tcp(local: SocketAddr, remote: SocketAddr, target: &str) -> Result<(), ActionError> {
+    use windows_sys::Win32::NetworkManagement::IpHelper::{
+        SetTcpEntry, MIB_TCPROW_LH, MIB_TCPROW_LH_0, MIB_TCP_STATE_DELETE_TCB,
+    };
+
+    let (SocketAddr::V4(local), SocketAddr::V4(remote)) = (local, remote) else {
+        return Err(ActionError::Unsupported(format!(
+            "{} (SetTcpEntry only supports IPv4)",
+            target
+        )));
+    };
+
+    let row = MIB_TCPROW_LH {
+        Anonymous: MIB_TCPROW_LH_0 {
+            State: MIB_TCP_STATE_DELETE_TCB,
+        },
+        dwLocalAddr: u32::from_ne_bytes(local.ip().octets()),
+        dwLocalPort: local.port().to_be() as u32,
+        dwRemoteAddr: u32::from_ne_bytes(remote.ip().octets()),
+        dwRemotePort: remote.port().to_be() as u32,
+    };
+
+    // SAFETY: `row` is a fully initialised MIB_TCPROW that outlives the call.
+    match unsafe { SetTcpEntry(&row) } {
+        0 => Ok(()),
+        code => Err(ActionError::from_io(
+            io::Error::from_raw_os_error(code as i32),
+            target,
+        )),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn close_tcp(_local: SocketAddr, _remote: SocketAddr, target: &str) -> Result<(), ActionError> {
+    Err(ActionError::Unsupported(target.to_string()))
+}
+
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    timestamp: u64,
+    action: &'a str,
+    target: &'a str,
+    outcome: String,
+}
+
+/// Append-only JSON lines log of every action taken.
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        AuditLog { path }
+    }
+
+    pub fn record(
+        &self,
+        action: &str,
+        target: &str,
+        result: &Result<(), ActionError>,
+    ) -> io::Result<()> {
+        let entry = AuditEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            action,
+            target,
+            outcome: match result {
+                Ok(()) => "ok".to_string(),
+                Err(err) => err.to_string(),
+            },
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_mapping() {
+        let err = ActionError::from_io(
+            io::Error::from(io::ErrorKind::PermissionDenied),
+            "process 4",
+        );
+        assert!(matches!(err, ActionError::PermissionDenied(_)));
+        assert!(err.to_string().contains("administrator"));
+
+        #[cfg(unix)]
+        assert!(matches!(
+            ActionError::from_io(io::Error::from_raw_os_error(libc::ESRCH), "process 4"),
+            ActionError::NotFound(_)
+        ));
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let path =
+            std::env::temp_dir().join(format!("net-process-audit-{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let log = AuditLog::new(path.clone());
+
+        log.record("kill_process", "1234", &Ok(())).unwrap();
+        log.record(
+            "close_connection",
+            "127.0.0.1:1 -> 127.0.0.1:2",
+            &Err(ActionError::NotFound("connection".to_string())),
+        )
+        .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["action"], "kill_process");
+        assert_eq!(lines[0]["outcome"], "ok");
+        assert_eq!(lines[1]["target"], "127.0.0.1:1 -> 127.0.0.1:2");
+        assert_eq!(lines[1]["outcome"], "not found: connection");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_process() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let reaper = std::thread::spawn(move || child.wait().unwrap());
+
+        assert_eq!(terminate_process(pid, Duration::from_secs(5)), Ok(()));
+        assert!(!reaper.join().unwrap().success());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_missing_process() {
+        assert!(matches!(
+            terminate_process(i32::MAX as u32, Duration::ZERO),
+            Err(ActionError::NotFound(_))
+        ));
+        assert!(matches!(
+            terminate_process(0, Duration::ZERO),
+            Err(ActionError::InvalidTarget(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_close_missing_connection() {
+        let result = close_connection(
+            "127.0.0.1:1".parse().unwrap(),
+            "127.0.0.1:2".parse().unwrap(),
+        );
+        assert!(
+            matches!(result, Err(ActionError::NotFound(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_close_connection() {
+        use std::io::Read;
+        use std::net::{TcpListener, TcpStream};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let _server = listener.accept().unwrap();
+
+        match close_connection(client.local_addr().unwrap(), client.peer_addr().unwrap()) {
+            Ok(()) => {
+                let mut buf = [0u8; 1];
+                assert!(client.read(&mut buf).is_err());
+            }
+            // unprivileged, or a kernel built without CONFIG_INET_DIAG_DESTROY
+            Err(ActionError::PermissionDenied(_)) | Err(ActionError::Unsupported(_)) => {}
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+}
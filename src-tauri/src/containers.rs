@@ -0,0 +1,238 @@
+//! Network namespaces and containers (Linux). Every process has a network
+//! namespace (`/proc/<pid>/ns/net`) and a control group (`/proc/<pid>/cgroup`),
+//! and container runtimes name the cgroup after the container. Connections
+//! and processes are tagged with both so they can be told apart, and grouped,
+//! by container.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::{Connection, Process};
+
+/// Key of the connections outside any container in
+/// `group_connections_by_container`.
+pub const HOST: &str = "host";
+
+/// Where a process runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Placement {
+    /// Inode of the network namespace.
+    pub net_namespace: Option<u64>,
+    /// cgroup v2 path, or the first v1 one, e.g. `/system.slice/ssh.service`.
+    pub cgroup: Option<String>,
+    pub container_id: Option<String>,
+}
+
+impl Placement {
+    /// The placement from the contents of `/proc/<pid>/ns/net` (link target)
+    /// and `/proc/<pid>/cgroup`, either of which we may not be allowed to read.
+    pub fn new(ns_link: Option<&str>, cgroup: Option<&str>) -> Self {
+        let cgroup = cgroup.and_then(parse_cgroup);
+        Placement {
+            net_namespace: ns_link.and_then(parse_ns_link),
+            container_id: cgroup.as_deref().and_then(container_id),
+            cgroup,
+        }
+    }
+}
+
+/// `net:[4026531840]` to `4026531840`.
+pub fn parse_ns_link(target: &str) -> Option<u64> {
+    target.split_once(":[")?.1.strip_suffix(']')?.parse().ok()
+}
+
+/// The cgroup path of a process: the unified (v2) hierarchy if it has one,
+/// otherwise the first v1 hierarchy the process is not at the root of.
+pub fn parse_cgroup(input: &str) -> Option<String> {
+    // hierarchy-ID:controller-list:cgroup-path
+    let paths: Vec<(&str, &str)> = input
+        .lines()
+        .filter_map(|line| {
+            let (id, rest) = line.split_once(':')?;
+            let (_, path) = rest.split_once(':')?;
+            Some((id, path))
+        })
+        .collect();
+
+    paths
+        .iter()
+        .find(|(id, path)| *id == "0" && *path != "/")
+        .or_else(|| paths.iter().find(|(_, path)| *path != "/"))
+        .or_else(|| paths.first())
+        .map(|(_, path)| path.to_string())
+}
+
+/// The container ID in a cgroup path. Docker, containerd, CRI-O and Podman
+/// all put the 64 hex digit ID in it, e.g. `/docker/<id>`,
+/// `/system.slice/docker-<id>.scope` or `/kubepods/.../cri-containerd-<id>.scope`.
+pub fn container_id(cgroup: &str) -> Option<String> {
+    cgroup.rsplit('/').find_map(|segment| {
+        let segment = segment.strip_suffix(".scope").unwrap_or(segment);
+        let id = segment.rsplit('-').next()?;
+        (id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())).then(|| id.to_string())
+    })
+}
+
+/// Fills in namespace, cgroup and container from the owning process.
+/// Sockets whose owner we could not see get the container of their
+/// namespace, when only one container uses it.
+pub fn tag_connections(connections: &mut [Connection], placements: &HashMap<String, Placement>) {
+    let mut by_namespace: HashMap<u64, Option<&Placement>> = HashMap::new();
+    for placement in placements.values() {
+        if let (Some(namespace), Some(id)) = (placement.net_namespace, &placement.container_id) {
+            by_namespace
+                .entry(namespace)
+                .and_modify(|known| {
+                    if known.is_some_and(|known| known.container_id.as_ref() != Some(id)) {
+                        *known = None;
+                    }
+                })
+                .or_insert(Some(placement));
+        }
+    }
+
+    for connection in connections {
+        let placement = placements.get(&connection.pid).or_else(|| {
+            by_namespace
+                .get(&connection.net_namespace?)
+                .copied()
+                .flatten()
+        });
+        let Some(placement) = placement else {
+            continue;
+        };
+        // a socket can outlive the namespace change of its owner, the
+        // namespace it was read from wins
+        if connection.net_namespace.is_none() {
+            connection.net_namespace = placement.net_namespace;
+        }
+        connection.cgroup = placement.cgroup.clone();
+        connection.container_id = placement.container_id.clone();
+    }
+}
+
+pub fn tag_processes(processes: &mut [Process], placements: &HashMap<String, Placement>) {
+    for process in processes {
+        if let Some(placement) = placements.get(&process.pid) {
+            process.net_namespace = placement.net_namespace;
+            process.cgroup = placement.cgroup.clone();
+            process.container_id = placement.container_id.clone();
+        }
+    }
+}
+
+/// Connections by container ID, `HOST` for those outside of any.
+pub fn group_connections_by_container(
+    connections: Vec<Connection>,
+) -> BTreeMap<String, Vec<Connection>> {
+    let mut connections_by_container: BTreeMap<String, Vec<Connection>> = BTreeMap::new();
+
+    for connection in connections {
+        let container = connection
+            .container_id
+            .clone()
+            .unwrap_or_else(|| HOST.to_string());
+        connections_by_container
+            .entry(container)
+            .or_default()
+            .push(connection);
+    }
+
+    connections_by_container
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_connections;
+
+    const ID: &str = "3f4e8a1b2c3d4e5f60718293a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3";
+
+    #[test]
+    fn test_parse_cgroup() {
+        assert_eq!(parse_ns_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_ns_link("/proc/1/ns/net"), None);
+
+        let v2 = format!("0::/system.slice/docker-{}.scope\n", ID);
+        assert_eq!(
+            parse_cgroup(&v2).as_deref(),
+            Some(format!("/system.slice/docker-{}.scope", ID).as_str())
+        );
+        assert_eq!(
+            container_id(&parse_cgroup(&v2).unwrap()).as_deref(),
+            Some(ID)
+        );
+
+        let v1 = format!(
+            "12:pids:/\n11:memory:/docker/{}\n1:name=systemd:/docker/{}\n0::/\n",
+            ID, ID
+        );
+        assert_eq!(parse_cgroup(&v1), Some(format!("/docker/{}", ID)));
+
+        let kubernetes = format!(
+            "/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod1234.slice/cri-containerd-{}.scope",
+            ID
+        );
+        assert_eq!(container_id(&kubernetes).as_deref(), Some(ID));
+        assert_eq!(
+            container_id(&format!("/machine.slice/libpod-{}.scope/container", ID)).as_deref(),
+            Some(ID)
+        );
+
+        assert_eq!(parse_cgroup("0::/\n").as_deref(), Some("/"));
+        assert_eq!(
+            container_id("/user.slice/user-1000.slice/session-2.scope"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tag_and_group() {
+        let placements = HashMap::from([
+            (
+                "100".to_string(),
+                Placement::new(Some("net:[1]"), Some("0::/user.slice\n")),
+            ),
+            (
+                "200".to_string(),
+                Placement::new(
+                    Some("net:[2]"),
+                    Some(&format!("0::/system.slice/docker-{}.scope\n", ID)),
+                ),
+            ),
+        ]);
+        let mut connections = parse_connections(
+            r#"
+        Active Internet connections (servers and established)
+        Proto Recv-Q Send-Q Local Address           Foreign Address         State       PID/Program name
+        tcp        0      0 0.0.0.0:80              0.0.0.0:*               LISTEN      100/nginx
+        tcp        0      0 0.0.0.0:80              0.0.0.0:*               LISTEN      200/nginx
+        tcp        0      0 0.0.0.0:80              0.0.0.0:*               LISTEN      -
+        tcp        0      0 0.0.0.0:80              0.0.0.0:*               LISTEN      -"#,
+        );
+        // the first namespace comes from its owner; the last two owners are
+        // not visible, but the first of them is in the container's namespace
+        let namespaces = [None, Some(2), Some(2), Some(3)];
+        for (connection, namespace) in connections.iter_mut().zip(namespaces) {
+            connection.net_namespace = namespace;
+        }
+        tag_connections(&mut connections, &placements);
+
+        assert_eq!(connections[0].net_namespace, Some(1));
+        assert_eq!(connections[0].cgroup.as_deref(), Some("/user.slice"));
+        assert_eq!(connections[0].container_id, None);
+        assert_eq!(connections[1].container_id.as_deref(), Some(ID));
+        assert_eq!(connections[2].container_id.as_deref(), Some(ID));
+        assert_eq!(connections[3].container_id, None);
+
+        let mut processes = vec![Process::new("nginx".to_string(), "200".to_string())];
+        tag_processes(&mut processes, &placements);
+        assert_eq!(processes[0].net_namespace, Some(2));
+        assert_eq!(processes[0].container_id.as_deref(), Some(ID));
+
+        let grouped = group_connections_by_container(connections);
+        assert_eq!(grouped[HOST].len(), 2);
+        assert_eq!(grouped[ID].len(), 2);
+    }
+}
@@ -0,0 +1,341 @@
+//! Reverse DNS for foreign addresses.
+//!
+//! Lookups never run on the sampling path: `DnsCache::annotate` only reads
+//! what is already cached and queues the misses, which are resolved on the
+//! blocking thread pool (at most `max_concurrent` at a time) and picked up by
+//! the next sample.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+use crate::Connection;
+
+/// Something that can turn an IP into a hostname. Blocking is fine, calls
+/// are made from the blocking thread pool.
+pub trait ReverseLookup: Send + Sync + 'static {
+    fn lookup(&self, ip: IpAddr) -> Option<String>;
+}
+
+/// Resolves through the OS resolver (`getnameinfo`).
+pub struct SystemLookup;
+
+impl ReverseLookup for SystemLookup {
+    fn lookup(&self, ip: IpAddr) -> Option<String> {
+        let hostname = dns_lookup::lookup_addr(&ip).ok()?;
+        // getnameinfo hands back the numeric form when there is no PTR record
+        if hostname.parse::<IpAddr>().is_ok() {
+            None
+        } else {
+            Some(hostname)
+        }
+    }
+}
+
+enum Entry {
+    Pending,
+    Resolved {
+        hostname: Option<String>,
+        expires: Instant,
+    },
+}
+
+/// Clears the `Pending` entry of a lookup that ends without a result, i.e. a
+/// task that panicked or was cancelled, so that it is scheduled again.
+struct PendingGuard {
+    entries: Arc<Mutex<HashMap<IpAddr, Entry>>>,
+    ip: IpAddr,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(Entry::Pending) = entries.get(&self.ip) {
+                entries.remove(&self.ip);
+            }
+        }
+    }
+}
+
+pub struct DnsCache<L: ReverseLookup> {
+    lookup: Arc<L>,
+    entries: Arc<Mutex<HashMap<IpAddr, Entry>>>,
+    permits: Arc<Semaphore>,
+    ttl: Duration,
+}
+
+impl<L: ReverseLookup> DnsCache<L> {
+    pub fn new(lookup: L, ttl: Duration, max_concurrent: usize) -> Self {
+        DnsCache {
+            lookup: Arc::new(lookup),
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            ttl,
+        }
+    }
+
+    /// Cached result for `ip`: `None` if it was never resolved (or expired),
+    /// `Some(None)` if it was resolved but has no name.
+    pub fn get(&self, ip: &IpAddr) -> Option<Option<String>> {
+        let entries = self.entries.lock().unwrap();
+        match entries.get(ip) {
+            Some(Entry::Resolved { hostname, expires }) if *expires > Instant::now() => {
+                Some(hostname.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Fills in `foreign_hostname` from the cache and queues a lookup for
+    /// every foreign address that is not cached yet. Returns the number of
+    /// lookups queued.
+    pub fn annotate(&self, connections: &mut [Connection]) -> usize {
+        let now = Instant::now();
+        self.entries.lock().unwrap().retain(|_, entry| match entry {
+            Entry::Pending => true,
+            Entry::Resolved { expires, .. } => *expires > now,
+        });
+
+        let mut scheduled = 0;
+        for connection in connections.iter_mut() {
+            let Some((ip, _)) = connection.foreign_endpoint() else {
+                continue;
+            };
+            if ip.is_unspecified() {
+                continue;
+            }
+
+            match self.get(&ip) {
+                Some(hostname) => connection.foreign_hostname = hostname,
+                None => {
+                    if self.schedule(ip).is_some() {
+                        scheduled += 1;
+                    }
+                }
+            }
+        }
+        scheduled
+    }
+
+    /// Queues a background lookup of `ip`, unless it is cached, already in
+    /// flight, or there is no tokio runtime to run it on.
+    pub fn schedule(&self, ip: IpAddr) -> Option<JoinHandle<()>> {
+        let handle = Handle::try_current().ok()?;
+
+        {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.get(&ip) {
+                Some(Entry::Pending) => return None,
+                Some(Entry::Resolved { expires, .. }) if *expires > Instant::now() => return None,
+                _ => {}
+            }
+            entries.insert(ip, Entry::Pending);
+        }
+
+        let lookup = self.lookup.clone();
+        let entries = self.entries.clone();
+        let permits = self.permits.clone();
+        let ttl = self.ttl;
+        let guard = PendingGuard {
+            entries: entries.clone(),
+            ip,
+        };
+
+        Some(handle.spawn(async move {
+            let _guard = guard;
+            let hostname = resolve_with(lookup, permits, ip).await;
+            entries.lock().unwrap().insert(
+                ip,
+                Entry::Resolved {
+                    hostname,
+                    expires: Instant::now() + ttl,
+                },
+            );
+        }))
+    }
+
+    /// Resolves `ip` now, going through the cache and the concurrency limit.
+    pub async fn resolve(&self, ip: IpAddr) -> Option<String> {
+        if let Some(hostname) = self.get(&ip) {
+            return hostname;
+        }
+
+        let hostname = resolve_with(self.lookup.clone(), self.permits.clone(), ip).await;
+        self.entries.lock().unwrap().insert(
+            ip,
+            Entry::Resolved {
+                hostname: hostname.clone(),
+                expires: Instant::now() + self.ttl,
+            },
+        );
+        hostname
+    }
+}
+
+async fn resolve_with<L: ReverseLookup>(
+    lookup: Arc<L>,
+    permits: Arc<Semaphore>,
+    ip: IpAddr,
+) -> Option<String> {
+    let _permit = permits.acquire_owned().await.ok()?;
+    tokio::task::spawn_blocking(move || lookup.lookup(ip))
+        .await
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionState, ConnectionType};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct MockLookup {
+        calls: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        delay: Duration,
+    }
+
+    impl ReverseLookup for Arc<MockLookup> {
+        fn lookup(&self, ip: IpAddr) -> Option<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            std::thread::sleep(self.delay);
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            match ip.to_string().as_str() {
+                "140.82.121.4" => Some("lb-140-82-121-4-fra.github.com".to_string()),
+                _ => None,
+            }
+        }
+    }
+
+    fn connection(foreign_address: &str) -> Connection {
+        Connection::new(
+            ConnectionType::Tcp,
+            "192.168.1.20:45678".to_string(),
+            foreign_address.to_string(),
+            ConnectionState::Established,
+            "2291".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resolve_is_cached() {
+        let mock = Arc::new(MockLookup::default());
+        let cache = DnsCache::new(mock.clone(), Duration::from_secs(60), 4);
+        let ip: IpAddr = "140.82.121.4".parse().unwrap();
+
+        assert_eq!(
+            cache.resolve(ip).await.as_deref(),
+            Some("lb-140-82-121-4-fra.github.com")
+        );
+        assert_eq!(
+            cache.resolve(ip).await.as_deref(),
+            Some("lb-140-82-121-4-fra.github.com")
+        );
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 1);
+
+        let unknown: IpAddr = "10.0.0.1".parse().unwrap();
+        assert_eq!(cache.resolve(unknown).await, None);
+        assert_eq!(cache.get(&unknown), Some(None));
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_resolved_again() {
+        let mock = Arc::new(MockLookup::default());
+        let cache = DnsCache::new(mock.clone(), Duration::ZERO, 4);
+        let ip: IpAddr = "140.82.121.4".parse().unwrap();
+
+        cache.resolve(ip).await;
+        assert_eq!(cache.get(&ip), None);
+        cache.resolve(ip).await;
+
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_annotate_reads_cache_and_queues_misses() {
+        let mock = Arc::new(MockLookup::default());
+        let cache = DnsCache::new(mock.clone(), Duration::from_secs(60), 4);
+        let mut connections = vec![
+            connection("140.82.121.4:443"),
+            connection("140.82.121.4:443"),
+            connection("0.0.0.0:0"),
+            connection("*:*"),
+        ];
+
+        // first sample: nothing cached, one lookup queued for the duplicate IP
+        assert_eq!(cache.annotate(&mut connections), 1);
+        assert!(connections[0].foreign_hostname.is_none());
+
+        let handle = cache.schedule("140.82.121.4".parse().unwrap());
+        assert!(handle.is_none(), "lookup is already in flight");
+
+        while cache.get(&"140.82.121.4".parse().unwrap()).is_none() {
+            tokio::task::yield_now().await;
+        }
+
+        // next sample picks the result up without another lookup
+        assert_eq!(cache.annotate(&mut connections), 0);
+        assert_eq!(
+            connections[1].foreign_hostname.as_deref(),
+            Some("lb-140-82-121-4-fra.github.com")
+        );
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrency_limit() {
+        let mock = Arc::new(MockLookup {
+            delay: Duration::from_millis(20),
+            ..Default::default()
+        });
+        let cache = DnsCache::new(mock.clone(), Duration::from_secs(60), 2);
+
+        let handles: Vec<_> = (1..=8)
+            .filter_map(|i| cache.schedule(format!("10.0.0.{}", i).parse().unwrap()))
+            .collect();
+        assert_eq!(handles.len(), 8);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 8);
+        assert!(mock.max_in_flight.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_lookup_is_scheduled_again() {
+        let mock = Arc::new(MockLookup::default());
+        let cache = DnsCache::new(mock.clone(), Duration::from_secs(60), 4);
+        let ip: IpAddr = "140.82.121.4".parse().unwrap();
+
+        let handle = cache.schedule(ip).unwrap();
+        handle.abort();
+        assert!(handle.await.unwrap_err().is_cancelled());
+
+        let handle = cache.schedule(ip).expect("no longer pending");
+        handle.await.unwrap();
+        assert_eq!(
+            cache.get(&ip),
+            Some(Some("lb-140-82-121-4-fra.github.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_annotate_without_runtime_does_not_panic() {
+        let cache = DnsCache::new(Arc::new(MockLookup::default()), Duration::from_secs(60), 1);
+        let mut connections = vec![connection("140.82.121.4:443")];
+
+        assert_eq!(cache.annotate(&mut connections), 0);
+    }
+}
@@ -0,0 +1,218 @@
+//! Offline GeoIP/ASN enrichment of foreign addresses.
+//!
+//! Reads user-supplied MaxMind DB (`.mmdb`) files, e.g. GeoLite2-Country and
+//! GeoLite2-ASN, so nothing ever leaves the machine. Results are cached per
+//! IP. Only public addresses are looked up, everything else (private,
+//! loopback, link-local, ...) is already described by `classify_address`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{classify_address, Connection};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GeoInfo {
+    /// ISO 3166-1 alpha-2 code, e.g. `DE`.
+    pub country_code: Option<String>,
+    pub country: Option<String>,
+    pub asn: Option<u32>,
+    pub organization: Option<String>,
+}
+
+/// A source of GeoIP data. `MmdbLookup` in production, mocked in tests.
+pub trait GeoLookup: Send + Sync {
+    fn lookup(&self, ip: IpAddr) -> Option<GeoInfo>;
+}
+
+/// Looks addresses up in one or more `.mmdb` files. Country and ASN data are
+/// merged, so separate Country and ASN databases can be combined.
+pub struct MmdbLookup {
+    readers: Vec<maxminddb::Reader<Vec<u8>>>,
+}
+
+impl MmdbLookup {
+    pub fn open<P: AsRef<Path>>(paths: &[P]) -> Result<Self, String> {
+        let readers = paths
+            .iter()
+            .map(|path| {
+                maxminddb::Reader::open_readfile(path)
+                    .map_err(|err| format!("{}: {}", path.as_ref().display(), err))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(MmdbLookup { readers })
+    }
+}
+
+// The subset of the GeoIP2 Country/City and GeoLite2 ASN schemas we use.
+#[derive(Deserialize)]
+struct MmdbRecord<'a> {
+    #[serde(borrow)]
+    country: Option<MmdbCountry<'a>>,
+    autonomous_system_number: Option<u32>,
+    autonomous_system_organization: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct MmdbCountry<'a> {
+    iso_code: Option<&'a str>,
+    #[serde(borrow)]
+    names: Option<BTreeMap<&'a str, &'a str>>,
+}
+
+impl GeoLookup for MmdbLookup {
+    fn lookup(&self, ip: IpAddr) -> Option<GeoInfo> {
+        let mut info = GeoInfo::default();
+        let mut found = false;
+
+        for reader in &self.readers {
+            let Ok(record) = reader.lookup::<MmdbRecord>(ip) else {
+                continue;
+            };
+            found = true;
+
+            if let Some(country) = record.country {
+                info.country_code = info
+                    .country_code
+                    .or_else(|| country.iso_code.map(str::to_string));
+                info.country = info.country.or_else(|| {
+                    country
+                        .names
+                        .and_then(|names| names.get("en").map(|name| name.to_string()))
+                });
+            }
+            info.asn = info.asn.or(record.autonomous_system_number);
+            info.organization = info
+                .organization
+                .or_else(|| record.autonomous_system_organization.map(str::to_string));
+        }
+
+        found.then_some(info)
+    }
+}
+
+pub struct GeoIpEnricher<L: GeoLookup> {
+    lookup: L,
+    cache: Mutex<HashMap<IpAddr, Option<GeoInfo>>>,
+}
+
+impl<L: GeoLookup> GeoIpEnricher<L> {
+    pub fn new(lookup: L) -> Self {
+        GeoIpEnricher {
+            lookup,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn lookup(&self, ip: IpAddr) -> Option<GeoInfo> {
+        if !classify_address(&ip).is_external() {
+            return None;
+        }
+
+        self.cache
+            .lock()
+            .unwrap()
+            .entry(ip)
+            .or_insert_with(|| self.lookup.lookup(ip))
+            .clone()
+    }
+
+    /// Sets `geo` on every connection with a foreign IP.
+    pub fn annotate(&self, connections: &mut [Connection]) {
+        for connection in connections.iter_mut() {
+            if let Some((ip, _)) = connection.foreign_endpoint() {
+                connection.geo = self.lookup(ip);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionState, ConnectionType};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct MockLookup {
+        calls: AtomicUsize,
+    }
+
+    impl GeoLookup for MockLookup {
+        fn lookup(&self, ip: IpAddr) -> Option<GeoInfo> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match ip.to_string().as_str() {
+                "140.82.121.4" => Some(GeoInfo {
+                    country_code: Some("DE".to_string()),
+                    country: Some("Germany".to_string()),
+                    asn: Some(36459),
+                    organization: Some("GITHUB".to_string()),
+                }),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_is_cached_and_skips_non_public() {
+        let enricher = GeoIpEnricher::new(MockLookup::default());
+
+        let github = enricher.lookup("140.82.121.4".parse().unwrap()).unwrap();
+        assert_eq!(github.country_code.as_deref(), Some("DE"));
+        assert_eq!(github.asn, Some(36459));
+        enricher.lookup("140.82.121.4".parse().unwrap());
+
+        assert_eq!(enricher.lookup("34.36.232.77".parse().unwrap()), None);
+        enricher.lookup("34.36.232.77".parse().unwrap());
+
+        assert_eq!(enricher.lookup("192.168.1.1".parse().unwrap()), None);
+        assert_eq!(enricher.lookup("fe80::1".parse().unwrap()), None);
+
+        assert_eq!(enricher.lookup.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_annotate() {
+        let enricher = GeoIpEnricher::new(MockLookup::default());
+        let mut connections = vec![
+            Connection::new(
+                ConnectionType::Tcp,
+                "192.168.100.14:59060".to_string(),
+                "140.82.121.4:443".to_string(),
+                ConnectionState::Established,
+                "9444".to_string(),
+            ),
+            Connection::new(
+                ConnectionType::Tcp,
+                "[::1]:1420".to_string(),
+                "[::1]:58750".to_string(),
+                ConnectionState::Established,
+                "11524".to_string(),
+            ),
+            Connection::new(
+                ConnectionType::Udp,
+                "0.0.0.0:5353".to_string(),
+                "*:*".to_string(),
+                ConnectionState::Close,
+                "3604".to_string(),
+            ),
+        ];
+
+        enricher.annotate(&mut connections);
+
+        assert_eq!(
+            connections[0].geo.as_ref().unwrap().organization.as_deref(),
+            Some("GITHUB")
+        );
+        assert_eq!(connections[1].geo, None);
+        assert_eq!(connections[2].geo, None);
+    }
+
+    #[test]
+    fn test_open_missing_database() {
+        assert!(MmdbLookup::open(&["/nonexistent/GeoLite2-Country.mmdb"]).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Importing command output pasted from machines we cannot run the app on:
+//! `netstat` (Windows or Linux), `ss -tanp`, `lsof -i` and `tasklist /fo
+//! csv`. The format is told by the header line, or, when that was not
+//! copied along, by which parser reads the most lines.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{
+    parse_connection_line, parse_lsof_line, parse_process_line, parse_ss_line, Connection, Process,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// `netstat -ano` (Windows) or `netstat -tanp` (Linux).
+    Netstat,
+    /// `ss -tanp`, optionally with `-u`.
+    Ss,
+    /// `lsof -i`.
+    Lsof,
+    /// `tasklist /fo csv`, with or without `/v`.
+    Tasklist,
+}
+
+const FORMATS: [Format; 4] = [Format::Netstat, Format::Ss, Format::Lsof, Format::Tasklist];
+
+impl Format {
+    fn is_header(self, line: &str) -> bool {
+        match self {
+            Format::Netstat => line.starts_with("Proto"),
+            Format::Ss => {
+                (line.starts_with("State") || line.starts_with("Netid")) && line.contains("Recv-Q")
+            }
+            Format::Lsof => line.starts_with("COMMAND") && line.contains("PID"),
+            // the column names are localized, but the PID column is never a number
+            Format::Tasklist => {
+                line.starts_with('"')
+                    && parse_process_line(line).is_some_and(|process| !is_pid(&process.pid))
+            }
+        }
+    }
+
+    fn has_connections(self) -> bool {
+        self != Format::Tasklist
+    }
+
+    fn has_processes(self) -> bool {
+        matches!(self, Format::Tasklist | Format::Lsof)
+    }
+
+    /// Adds `line` to `file`, `false` when it is not a row of this format.
+    fn parse_line(self, line: &str, file: &mut ImportedFile) -> bool {
+        let connection = match self {
+            Format::Netstat => parse_connection_line(line),
+            Format::Ss => parse_ss_line(line),
+            Format::Lsof => parse_lsof_line(line).map(|(connection, process)| {
+                if !file.processes.iter().any(|known| known.pid == process.pid) {
+                    file.processes.push(process);
+                }
+                connection
+            }),
+            Format::Tasklist => {
+                return match parse_process_line(line).filter(|process| is_pid(&process.pid)) {
+                    Some(process) => {
+                        file.processes.push(process);
+                        true
+                    }
+                    None => false,
+                };
+            }
+        };
+        match connection {
+            Some(connection) => {
+                file.connections.push(connection);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn is_pid(pid: &str) -> bool {
+    !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedLine {
+    /// 1-based, as an editor shows it.
+    pub line: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    /// File name, as given to `import_text`.
+    pub name: String,
+    pub format: Format,
+    /// Rows read as a connection or process.
+    pub parsed: usize,
+    /// Non-empty lines after the header that are not a row.
+    pub skipped: Vec<SkippedLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedFile {
+    pub report: ImportReport,
+    pub connections: Vec<Connection>,
+    pub processes: Vec<Process>,
+}
+
+impl ImportedFile {
+    fn new(name: &str, format: Format) -> Self {
+        ImportedFile {
+            report: ImportReport {
+                name: name.to_string(),
+                format,
+                parsed: 0,
+                skipped: Vec::new(),
+            },
+            connections: Vec::new(),
+            processes: Vec::new(),
+        }
+    }
+}
+
+/// The format of `input`, `None` when no parser can read any of it.
+pub fn detect_format(input: &str) -> Option<Format> {
+    let lines = || input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    let header = lines().find_map(|line| FORMATS.into_iter().find(|format| format.is_header(line)));
+    if header.is_some() {
+        return header;
+    }
+
+    FORMATS
+        .into_iter()
+        .map(|format| {
+            let mut file = ImportedFile::new("", format);
+            let rows = lines()
+                .filter(|line| format.parse_line(line, &mut file))
+                .count();
+            (format, rows)
+        })
+        .filter(|(_, rows)| *rows > 0)
+        .max_by_key(|(_, rows)| *rows)
+        .map(|(format, _)| format)
+}
+
+/// Runs `input` through the parser of its format. Anything before the
+/// header (e.g. `Active Connections`) is left out of the report.
+pub fn import_text(name: &str, input: &str) -> Result<ImportedFile, String> {
+    let format = detect_format(input).ok_or(format!("{}: unrecognized format", name))?;
+    let mut file = ImportedFile::new(name, format);
+
+    let lines: Vec<(usize, &str)> = input
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .collect();
+    let start = lines
+        .iter()
+        .position(|(_, line)| format.is_header(line))
+        .map_or(0, |header| header + 1);
+
+    for (index, line) in &lines[start..] {
+        if format.parse_line(line, &mut file) {
+            file.report.parsed += 1;
+        } else {
+            file.report.skipped.push(SkippedLine {
+                line: index + 1,
+                text: line.to_string(),
+            });
+        }
+    }
+
+    Ok(file)
+}
+
+/// Reads and imports the file at `path`. Output redirected in PowerShell
+/// is UTF-16, that is decoded too.
+pub fn open(path: &Path) -> Result<ImportedFile, String> {
+    let bytes = fs::read(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    );
+    import_text(&name, &decode(&bytes))
+}
+
+fn decode(bytes: &[u8]) -> String {
+    match bytes {
+        [0xff, 0xfe, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xef, 0xbb, 0xbf, rest @ ..] => String::from_utf8_lossy(rest).to_string(),
+        _ => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+/// Connections and processes from imported files, shown instead of the
+/// system's. A file only replaces what its format has rows for, so a
+/// `netstat` and a `tasklist` file can be looked at together, while an
+/// `lsof` file replaces both.
+#[derive(Debug, Default)]
+pub struct Import {
+    pub connections: Vec<Connection>,
+    pub processes: Vec<Process>,
+    pub reports: Vec<ImportReport>,
+}
+
+impl Import {
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    pub fn add(&mut self, file: ImportedFile) {
+        let format = file.report.format;
+        if format.has_connections() {
+            self.connections = file.connections;
+        }
+        if format.has_processes() {
+            self.processes = file.processes;
+        }
+        // keep the reports of files that still provide something
+        self.reports.retain(|report| {
+            (report.format.has_connections() && !format.has_connections())
+                || (report.format.has_processes() && !format.has_processes())
+        });
+        self.reports.push(file.report);
+    }
+}
+
+/// `report` as text for the terminal, one line per skipped line.
+pub fn format_report(report: &ImportReport) -> String {
+    let mut out = format!(
+        "{}: {} rows ({:?}), {} lines skipped\n",
+        report.name,
+        report.parsed,
+        report.format,
+        report.skipped.len()
+    );
+    for skipped in &report.skipped {
+        out.push_str(&format!("  line {}: {}\n", skipped.line, skipped.text));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_windows_netstat() {
+        let input = "
+Active Connections
+
+  Proto  Local Address          Foreign Address        State           PID
+  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1004
+ [svchost.exe]
+  TCP    192.168.1.5:50432      140.82.112.4:443       ESTABLISHED     7320
+  UDP    0.0.0.0:5353           *:*                                    2216
+";
+        let file = import_text("netstat.txt", input).unwrap();
+        assert_eq!(file.report.format, Format::Netstat);
+        assert_eq!(file.report.parsed, 3);
+        assert_eq!(
+            file.report.skipped,
+            vec![SkippedLine {
+                line: 6,
+                text: "[svchost.exe]".to_string()
+            }]
+        );
+        assert_eq!(file.connections[1].pid, "7320");
+        assert!(file.processes.is_empty());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let ss = "State  Recv-Q Send-Q Local Address:Port Peer Address:Port Process
+LISTEN 0      128    0.0.0.0:22         0.0.0.0:*          users:((\"sshd\",pid=812,fd=3))
+";
+        let lsof = "COMMAND  PID USER   FD   TYPE DEVICE SIZE/OFF NODE NAME
+sshd     812 root    3u  IPv4  18432      0t0  TCP *:22 (LISTEN)
+";
+        let tasklist = "\"Abbildname\",\"PID\",\"Sitzungsname\",\"Sitz.-Nr.\",\"Speichernutzung\"
+\"System\",\"4\",\"Services\",\"0\",\"144 K\"
+";
+        let linux_netstat = "Active Internet connections (servers and established)
+Proto Recv-Q Send-Q Local Address           Foreign Address         State       PID/Program name
+tcp        0      0 0.0.0.0:22              0.0.0.0:*               LISTEN      812/sshd
+";
+        assert_eq!(detect_format(ss), Some(Format::Ss));
+        assert_eq!(detect_format(lsof), Some(Format::Lsof));
+        assert_eq!(detect_format(tasklist), Some(Format::Tasklist));
+        assert_eq!(detect_format(linux_netstat), Some(Format::Netstat));
+
+        // headers not copied along
+        let headless = "tcp  0  0 10.0.0.2:22  10.0.0.9:50022  ESTABLISHED 901/sshd\n";
+        assert_eq!(detect_format(headless), Some(Format::Netstat));
+        let headless = "\"System\",\"4\",\"Services\",\"0\",\"144 K\"\n";
+        assert_eq!(detect_format(headless), Some(Format::Tasklist));
+        let headless = "ESTAB 0 0 10.0.0.2:22 10.0.0.9:50022 users:((\"sshd\",pid=901,fd=4))\n";
+        assert_eq!(detect_format(headless), Some(Format::Ss));
+        assert_eq!(detect_format("hello world\n"), None);
+
+        let file = import_text("tasklist.csv", tasklist).unwrap();
+        assert_eq!(file.report.parsed, 1);
+        assert_eq!(file.processes[0].image_name, "System");
+    }
+
+    #[test]
+    fn test_import_replaces_by_kind() {
+        let netstat = import_text(
+            "a.txt",
+            "  TCP    0.0.0.0:135    0.0.0.0:0    LISTENING    1004\n",
+        )
+        .unwrap();
+        let tasklist =
+            import_text("b.csv", "\"System\",\"4\",\"Services\",\"0\",\"144 K\"\n").unwrap();
+        let ss = import_text("c.txt", "ESTAB 0 0 10.0.0.2:22 10.0.0.9:50022\n").unwrap();
+        let lsof = import_text(
+            "d.txt",
+            "sshd 812 root 3u IPv4 18432 0t0 TCP *:22 (LISTEN)\n",
+        )
+        .unwrap();
+        let names = |import: &Import| -> Vec<String> {
+            import
+                .reports
+                .iter()
+                .map(|report| report.name.clone())
+                .collect()
+        };
+
+        let mut import = Import::default();
+        assert!(import.is_empty());
+        import.add(netstat);
+        import.add(tasklist);
+        import.add(ss);
+        assert_eq!(names(&import), ["b.csv", "c.txt"]);
+        assert_eq!(import.connections[0].local_address, "10.0.0.2:22");
+        assert_eq!(import.processes[0].image_name, "System");
+
+        // lsof has both
+        import.add(lsof);
+        assert_eq!(names(&import), ["d.txt"]);
+        assert_eq!(import.connections[0].pid, "812");
+        assert_eq!(import.processes.len(), 1);
+        assert_eq!(import.processes[0].image_name, "sshd");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(b"\xef\xbb\xbfProto"), "Proto");
+        assert_eq!(decode(b"\xff\xfeP\x00r\x00"), "Pr");
+        assert_eq!(decode(b"Proto"), "Proto");
+    }
+}
@@ -0,0 +1,296 @@
+//! Per-adapter statistics. `netstat -e` only prints one aggregate for all
+//! interfaces, this reads the counters of every interface on its own from
+//! `/proc/net/dev` and `/sys/class/net` on Linux and `GetIfTable2` on
+//! Windows.
+
+use std::io;
+use std::net::IpAddr;
+
+use serde::Serialize;
+
+use crate::NetworkData;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NetworkInterface {
+    pub name: String,
+    /// `aa:bb:cc:dd:ee:ff`, `None` for interfaces without a hardware address.
+    pub mac: Option<String>,
+    pub mtu: Option<u32>,
+    pub up: bool,
+    pub addresses: Vec<IpAddr>,
+    pub bytes: NetworkData,
+    pub packets: NetworkData,
+    pub errors: NetworkData,
+    pub drops: NetworkData,
+}
+
+/// Parses `/proc/net/dev`. Only the counters are filled in.
+pub fn parse_proc_net_dev(input: &str) -> Vec<NetworkInterface> {
+    input
+        .lines()
+        .filter_map(|line| {
+            // the two header lines have no colon
+            let (name, counters) = line.split_once(':')?;
+            let counters: Vec<u64> = counters
+                .split_whitespace()
+                .map(|value| value.parse().ok())
+                .collect::<Option<_>>()?;
+            // receive: bytes packets errs drop fifo frame compressed multicast
+            // transmit: bytes packets errs drop fifo colls carrier compressed
+            if counters.len() < 16 {
+                return None;
+            }
+
+            let pair = |rx: usize| NetworkData {
+                received: counters[rx],
+                sent: counters[rx + 8],
+            };
+            Some(NetworkInterface {
+                name: name.trim().to_string(),
+                bytes: pair(0),
+                packets: pair(1),
+                errors: pair(2),
+                drops: pair(3),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Formats a hardware address, `None` when it is empty or all zeros (as for
+/// loopback).
+pub fn format_mac(bytes: &[u8]) -> Option<String> {
+    if bytes.iter().all(|b| *b == 0) {
+        return None;
+    }
+    Some(
+        bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
+/// Parses the hex `flags` file in `/sys/class/net/<name>`.
+pub fn parse_sysfs_flags(input: &str) -> Option<u32> {
+    u32::from_str_radix(input.trim().trim_start_matches("0x"), 16).ok()
+}
+
+#[cfg(target_os = "linux")]
+pub fn interfaces() -> io::Result<Vec<NetworkInterface>> {
+    use std::fs;
+    use std::path::Path;
+
+    let mut interfaces = parse_proc_net_dev(&fs::read_to_string("/proc/net/dev")?);
+    let mut addresses = interface_addresses().unwrap_or_default();
+
+    for interface in &mut interfaces {
+        let dir = Path::new("/sys/class/net").join(&interface.name);
+        let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+
+        interface.mac = read("address").and_then(|mac| {
+            let bytes: Option<Vec<u8>> = mac
+                .trim()
+                .split(':')
+                .map(|b| u8::from_str_radix(b, 16).ok())
+                .collect();
+            bytes.and_then(|bytes| format_mac(&bytes))
+        });
+        interface.mtu = read("mtu").and_then(|mtu| mtu.trim().parse().ok());
+        interface.up = read("flags")
+            .and_then(|flags| parse_sysfs_flags(&flags))
+            .is_some_and(|flags| flags & libc::IFF_UP as u32 != 0);
+        interface.addresses = addresses.remove(&interface.name).unwrap_or_default();
+    }
+
+    Ok(interfaces)
+}
+
+/// Addresses by interface name, from `getifaddrs`.
+#[cfg(target_os = "linux")]
+fn interface_addresses() -> io::Result<std::collections::HashMap<String, Vec<IpAddr>>> {
+    use std::collections::HashMap;
+    use std::ffi::CStr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    let mut addresses: HashMap<String, Vec<IpAddr>> = HashMap::new();
+
+    // SAFETY: the list is only walked between a successful getifaddrs and
+    // freeifaddrs, and every sockaddr is cast according to its family.
+    unsafe {
+        let mut list = std::ptr::null_mut();
+        if libc::getifaddrs(&mut list) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut entry = list;
+        while let Some(ifaddr) = entry.as_ref() {
+            entry = ifaddr.ifa_next;
+            let Some(addr) = ifaddr.ifa_addr.as_ref() else {
+                continue;
+            };
+
+            let ip = match addr.sa_family as i32 {
+                libc::AF_INET => {
+                    let addr = &*(ifaddr.ifa_addr as *const libc::sockaddr_in);
+                    IpAddr::V4(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)))
+                }
+                libc::AF_INET6 => {
+                    let addr = &*(ifaddr.ifa_addr as *const libc::sockaddr_in6);
+                    IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr))
+                }
+                _ => continue,
+            };
+
+            // IPv4 aliases are labelled `eth0:1`
+            let name = CStr::from_ptr(ifaddr.ifa_name).to_string_lossy();
+            let name = name.split(':').next().unwrap_or_default().to_string();
+            addresses.entry(name).or_default().push(ip);
+        }
+
+        libc::freeifaddrs(list);
+    }
+
+    Ok(addresses)
+}
+
+#[cfg(windows)]
+pub fn interfaces() -> io::Result<Vec<NetworkInterface>> {
+    use std::collections::HashMap;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::{ptr, slice};
+    use windows_sys::Win32::Foundation::NO_ERROR;
+    use windows_sys::Win32::NetworkManagement::IpHelper::{
+        FreeMibTable, GetIfTable2, GetUnicastIpAddressTable, MIB_IF_TABLE2,
+        MIB_UNICASTIPADDRESS_TABLE,
+    };
+    use windows_sys::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+    use windows_sys::Win32::Networking::WinSock::{AF_INET, AF_INET6, AF_UNSPEC};
+
+    // bit 1 of InterfaceAndOperStatusFlags, set for the filter drivers that
+    // GetIfTable2 lists next to every real adapter
+    const FILTER_INTERFACE: u8 = 0x2;
+
+    let mut addresses: HashMap<u32, Vec<IpAddr>> = HashMap::new();
+    let mut interfaces = Vec::new();
+
+    // SAFETY: both tables are only read between a successful Get* call and
+    // FreeMibTable, and NumEntries bounds the rows. SOCKADDR_INET is read
+    // through the member matching its family.
+    unsafe {
+        let mut table: *mut MIB_UNICASTIPADDRESS_TABLE = ptr::null_mut();
+        if GetUnicastIpAddressTable(AF_UNSPEC, &mut table) == NO_ERROR {
+            let rows = slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize);
+            for row in rows {
+                let ip = match row.Address.si_family {
+                    AF_INET => IpAddr::V4(Ipv4Addr::from(u32::from_be(
+                        row.Address.Ipv4.sin_addr.S_un.S_addr,
+                    ))),
+                    AF_INET6 => IpAddr::V6(Ipv6Addr::from(row.Address.Ipv6.sin6_addr.u.Byte)),
+                    _ => continue,
+                };
+                addresses.entry(row.InterfaceIndex).or_default().push(ip);
+            }
+            FreeMibTable(table as *const _);
+        }
+
+        let mut table: *mut MIB_IF_TABLE2 = ptr::null_mut();
+        let code = GetIfTable2(&mut table);
+        if code != NO_ERROR {
+            return Err(io::Error::from_raw_os_error(code as i32));
+        }
+        let rows = slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize);
+        for row in rows {
+            if row.InterfaceAndOperStatusFlags._bitfield & FILTER_INTERFACE != 0 {
+                continue;
+            }
+
+            let alias_len = row
+                .Alias
+                .iter()
+                .position(|c| *c == 0)
+                .unwrap_or(row.Alias.len());
+            let mac_len = (row.PhysicalAddressLength as usize).min(row.PhysicalAddress.len());
+            interfaces.push(NetworkInterface {
+                name: String::from_utf16_lossy(&row.Alias[..alias_len]),
+                mac: format_mac(&row.PhysicalAddress[..mac_len]),
+                mtu: Some(row.Mtu),
+                up: row.OperStatus == IfOperStatusUp,
+                addresses: addresses.remove(&row.InterfaceIndex).unwrap_or_default(),
+                bytes: NetworkData {
+                    received: row.InOctets,
+                    sent: row.OutOctets,
+                },
+                packets: NetworkData {
+                    received: row.InUcastPkts + row.InNUcastPkts,
+                    sent: row.OutUcastPkts + row.OutNUcastPkts,
+                },
+                errors: NetworkData {
+                    received: row.InErrors,
+                    sent: row.OutErrors,
+                },
+                drops: NetworkData {
+                    received: row.InDiscards,
+                    sent: row.OutDiscards,
+                },
+            });
+        }
+        FreeMibTable(table as *const _);
+    }
+
+    Ok(interfaces)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn interfaces() -> io::Result<Vec<NetworkInterface>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "per-interface statistics are not supported on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_dev() {
+        let input = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 8302212   79054    0    0    0     0          0         0  8302212   79054    0    0    0     0       0          0
+  eth0:1893449342 1527811    3   12    0     0          0      1204 103441125  784112    0    1    0     0       0          0
+";
+        let interfaces = parse_proc_net_dev(input);
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(interfaces[0].name, "lo");
+        assert_eq!(interfaces[0].bytes.received, 8302212);
+
+        let eth0 = &interfaces[1];
+        assert_eq!(eth0.name, "eth0");
+        assert_eq!(eth0.bytes.received, 1893449342);
+        assert_eq!(eth0.bytes.sent, 103441125);
+        assert_eq!(eth0.packets.sent, 784112);
+        assert_eq!(eth0.errors.received, 3);
+        assert_eq!(eth0.drops.received, 12);
+        assert_eq!(eth0.drops.sent, 1);
+        assert_eq!(eth0.mac, None);
+    }
+
+    #[test]
+    fn test_format_mac() {
+        assert_eq!(
+            format_mac(&[0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0xfe]).as_deref(),
+            Some("00:1a:2b:3c:4d:fe")
+        );
+        assert_eq!(format_mac(&[0; 6]), None);
+        assert_eq!(format_mac(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_sysfs_flags() {
+        assert_eq!(parse_sysfs_flags("0x1003\n"), Some(0x1003));
+        assert_eq!(parse_sysfs_flags("0x1002"), Some(0x1002));
+        assert_eq!(parse_sysfs_flags("up"), None);
+    }
+}
@@ -1,146 +1,378 @@
-use std::collections::HashMap;
-
-use serde::Serialize;
-
-#[derive(Debug, Serialize)]
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::interfaces::NetworkInterface;
+
+pub mod actions;
+pub mod containers;
+pub mod dns;
+pub mod geoip;
+pub mod import;
+pub mod interfaces;
+pub mod listening;
+pub mod port_lookup;
+pub mod process_details;
+pub mod process_tree;
+#[cfg(target_os = "linux")]
+pub mod procfs;
+pub mod recording;
+pub mod rules;
+pub mod services;
+#[cfg(target_os = "linux")]
+pub mod sock_diag;
+pub mod thresholds;
+pub mod users;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NetworkData {
     received: u64,
     sent: u64,
 }
 
-#[derive(Debug, Serialize)]
-pub struct InterfaceStats {
-    bytes: NetworkData,
-    unicast_packets: NetworkData,
-    non_unicast_packets: NetworkData,
-    discards: NetworkData,
-    errors: NetworkData,
-    unknown_protocols: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct IpV4Statistics {
-    packets_received: u64,
-    received_header_errors: u64,
-    received_address_errors: u64,
-    datagrams_forwarded: u64,
-    unknown_protocols_received: u64,
-    received_packets_discarded: u64,
-    received_packets_delivered: u64,
-    output_requests: u64,
-    routing_discards: u64,
-    discarded_output_packets: u64,
-    output_packet_no_route: u64,
-    reassembly_required: u64,
-    reassembly_successful: u64,
-    reassembly_failures: u64,
-    datagrams_successfully_fragmented: u64,
-    datagrams_failing_fragmentation: u64,
-    fragments_created: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct IpV6Statistics {
-    packets_received: u64,
-    received_header_errors: u64,
-    received_address_errors: u64,
-    datagrams_forwarded: u64,
-    unknown_protocols_received: u64,
-    received_packets_discarded: u64,
-    received_packets_delivered: u64,
-    output_requests: u64,
-    routing_discards: u64,
-    discarded_output_packets: u64,
-    output_packet_no_route: u64,
-    reassembly_required: u64,
-    reassembly_successful: u64,
-    reassembly_failures: u64,
-    datagrams_successfully_fragmented: u64,
-    datagrams_failing_fragmentation: u64,
-    fragments_created: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct IcmpV4Statistics {
-    messages: NetworkData,
-    errors: NetworkData,
-    destination_unreachable: NetworkData,
-    time_exceeded: NetworkData,
-    parameter_problems: NetworkData,
-    source_quenches: NetworkData,
-    redirects: NetworkData,
-    echo_replies: NetworkData,
-    echos: NetworkData,
-    timestamps: NetworkData,
-    timestamp_replies: NetworkData,
-    address_masks: NetworkData,
-    address_mask_replies: NetworkData,
-    router_solicitations: NetworkData,
-    router_advertisements: NetworkData,
-}
-
-#[derive(Debug, Serialize)]
-pub struct IcmpV6Statistics {
-    messages: NetworkData,
-    errors: NetworkData,
-    destination_unreachable: NetworkData,
-    packet_too_big: NetworkData,
-    time_exceeded: NetworkData,
-    parameter_problems: NetworkData,
-    echos: NetworkData,
-    echo_replies: NetworkData,
-    mld_queries: NetworkData,
-    mld_reports: NetworkData,
-    mld_dones: NetworkData,
-    router_solicitations: NetworkData,
-    router_advertisements: NetworkData,
-    neighbor_solicitations: NetworkData,
-    neighbor_advertisements: NetworkData,
-    redirects: NetworkData,
-    router_renumberings: NetworkData,
-}
-
-#[derive(Debug, Serialize)]
-pub struct TcpV4Statistics {
-    active_opens: u64,
-    passive_opens: u64,
-    failed_connection_attempts: u64,
-    reset_connections: u64,
-    current_connections: u64,
-    segments_received: u64,
-    segments_sent: u64,
-    segments_retransmitted: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct TcpV6Statistics {
-    active_opens: u64,
-    passive_opens: u64,
-    failed_connection_attempts: u64,
-    reset_connections: u64,
-    current_connections: u64,
-    segments_received: u64,
-    segments_sent: u64,
-    segments_retransmitted: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct UdpV4Statistics {
-    datagrams_received: u64,
-    no_ports: u64,
-    receive_errors: u64,
-    datagrams_sent: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct UdpV6Statistics {
-    datagrams_received: u64,
-    no_ports: u64,
-    receive_errors: u64,
-    datagrams_sent: u64,
-}
-
-#[derive(Debug, Serialize)]
+/// A counter field of a `stats_struct!`, as described by its definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StatField {
+    pub name: &'static str,
+    /// The row label in `netstat -s`.
+    pub label: &'static str,
+    /// `Section.Name` in `/proc/net/snmp` or `/proc/net/snmp6`: one key for
+    /// a count, the received and the sent key for a pair. Empty when Linux
+    /// has no such counter.
+    pub snmp: &'static [&'static str],
+    pub unit: &'static str,
+    pub description: &'static str,
+    pub pair: bool,
+}
+
+/// Field types a `stats_struct!` can hold.
+trait StatType: Default {
+    const PAIR: bool;
+
+    fn from_stat(value: Option<StatValue>) -> Self;
+}
+
+impl StatType for u64 {
+    const PAIR: bool = false;
+
+    fn from_stat(value: Option<StatValue>) -> Self {
+        match value {
+            Some(StatValue::Count(count)) => count,
+            _ => 0,
+        }
+    }
+}
+
+impl StatType for NetworkData {
+    const PAIR: bool = true;
+
+    fn from_stat(value: Option<StatValue>) -> Self {
+        match value {
+            Some(StatValue::Pair(pair)) => pair,
+            _ => NetworkData::default(),
+        }
+    }
+}
+
+/// Implemented by every `stats_struct!`.
+pub trait StatsStruct: Default {
+    /// The section header in `netstat -s`.
+    const TITLE: &'static str;
+    const FIELDS: &'static [StatField];
+
+    fn from_section(section: &StatsSection) -> Self;
+}
+
+/// Defines a section of `NetworkStatistics` once: the struct, its
+/// `StatsStruct` metadata, how it is read from a `StatsSnapshot` section and
+/// how it is displayed. Every field has a one line doc comment (its
+/// description) and is followed by its netstat label, Linux SNMP keys and
+/// unit.
+macro_rules! stats_struct {
+    (
+        $name:ident, $title:literal {
+            $(
+                #[doc = $description:literal]
+                $field:ident: $ty:ty = $label:literal, [$($snmp:literal),*], $unit:literal;
+            )*
+        }
+    ) => {
+        #[derive(Debug, Default, Serialize)]
+        pub struct $name {
+            $(
+                #[doc = $description]
+                $field: $ty,
+            )*
+        }
+
+        impl StatsStruct for $name {
+            const TITLE: &'static str = $title;
+            const FIELDS: &'static [StatField] = &[
+                $(
+                    StatField {
+                        name: stringify!($field),
+                        label: $label,
+                        snmp: &[$($snmp),*],
+                        unit: $unit,
+                        description: $description.trim_ascii(),
+                        pair: <$ty as StatType>::PAIR,
+                    },
+                )*
+            ];
+
+            fn from_section(section: &StatsSection) -> Self {
+                $name {
+                    $(
+                        $field: StatType::from_stat(
+                            section.get(&stats_key($label.split_whitespace())),
+                        ),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+stats_struct! {
+    InterfaceStats, "Interface Statistics" {
+        /// Bytes received and sent on all adapters.
+        bytes: NetworkData = "Bytes", [], "bytes";
+        /// Packets addressed to a single host.
+        unicast_packets: NetworkData = "Unicast packets", [], "packets";
+        /// Broadcast and multicast packets.
+        non_unicast_packets: NetworkData = "Non-unicast packets", [], "packets";
+        /// Packets dropped without an error, e.g. for lack of buffer space.
+        discards: NetworkData = "Discards", [], "packets";
+        /// Packets dropped because they were malformed.
+        errors: NetworkData = "Errors", [], "packets";
+        /// Packets for a protocol the host does not speak.
+        unknown_protocols: u64 = "Unknown protocols", [], "packets";
+    }
+}
+
+stats_struct! {
+    IpV4Statistics, "IPv4 Statistics" {
+        /// Datagrams received, including the ones in error.
+        packets_received: u64 = "Packets Received", ["Ip.InReceives"], "packets";
+        /// Datagrams dropped for a bad header (checksum, version, TTL, ...).
+        received_header_errors: u64 = "Received Header Errors", ["Ip.InHdrErrors"], "packets";
+        /// Datagrams dropped because the destination is not a local address.
+        received_address_errors: u64 = "Received Address Errors", ["Ip.InAddrErrors"], "packets";
+        /// Datagrams routed on to another host.
+        datagrams_forwarded: u64 = "Datagrams Forwarded", ["Ip.ForwDatagrams"], "packets";
+        /// Datagrams for a transport protocol the host does not speak.
+        unknown_protocols_received: u64 = "Unknown Protocols Received", ["Ip.InUnknownProtos"], "packets";
+        /// Valid datagrams dropped, e.g. for lack of buffer space.
+        received_packets_discarded: u64 = "Received Packets Discarded", ["Ip.InDiscards"], "packets";
+        /// Datagrams handed to a transport protocol.
+        received_packets_delivered: u64 = "Received Packets Delivered", ["Ip.InDelivers"], "packets";
+        /// Datagrams the host asked to send, forwarded ones excluded.
+        output_requests: u64 = "Output Requests", ["Ip.OutRequests"], "packets";
+        /// Routing entries dropped although they were valid.
+        routing_discards: u64 = "Routing Discards", [], "packets";
+        /// Valid outgoing datagrams dropped, e.g. for lack of buffer space.
+        discarded_output_packets: u64 = "Discarded Output Packets", ["Ip.OutDiscards"], "packets";
+        /// Outgoing datagrams dropped because no route matched.
+        output_packet_no_route: u64 = "Output Packet No Route", ["Ip.OutNoRoutes"], "packets";
+        /// Fragments received that needed reassembly.
+        reassembly_required: u64 = "Reassembly Required", ["Ip.ReasmReqds"], "fragments";
+        /// Datagrams reassembled from fragments.
+        reassembly_successful: u64 = "Reassembly Successful", ["Ip.ReasmOKs"], "packets";
+        /// Reassemblies that failed, e.g. on a timeout.
+        reassembly_failures: u64 = "Reassembly Failures", ["Ip.ReasmFails"], "packets";
+        /// Datagrams split into fragments.
+        datagrams_successfully_fragmented: u64 = "Datagrams Successfully Fragmented", ["Ip.FragOKs"], "packets";
+        /// Datagrams dropped because they needed fragmenting but had Don't Fragment set.
+        datagrams_failing_fragmentation: u64 = "Datagrams Failing Fragmentation", ["Ip.FragFails"], "packets";
+        /// Fragments generated by fragmentation.
+        fragments_created: u64 = "Fragments Created", ["Ip.FragCreates"], "fragments";
+    }
+}
+
+stats_struct! {
+    IpV6Statistics, "IPv6 Statistics" {
+        /// Datagrams received, including the ones in error.
+        packets_received: u64 = "Packets Received", ["Ip6.InReceives"], "packets";
+        /// Datagrams dropped for a bad header (version, hop limit, options, ...).
+        received_header_errors: u64 = "Received Header Errors", ["Ip6.InHdrErrors"], "packets";
+        /// Datagrams dropped because the destination is not a local address.
+        received_address_errors: u64 = "Received Address Errors", ["Ip6.InAddrErrors"], "packets";
+        /// Datagrams routed on to another host.
+        datagrams_forwarded: u64 = "Datagrams Forwarded", ["Ip6.OutForwDatagrams"], "packets";
+        /// Datagrams for a transport protocol the host does not speak.
+        unknown_protocols_received: u64 = "Unknown Protocols Received", ["Ip6.InUnknownProtos"], "packets";
+        /// Valid datagrams dropped, e.g. for lack of buffer space.
+        received_packets_discarded: u64 = "Received Packets Discarded", ["Ip6.InDiscards"], "packets";
+        /// Datagrams handed to a transport protocol.
+        received_packets_delivered: u64 = "Received Packets Delivered", ["Ip6.InDelivers"], "packets";
+        /// Datagrams the host asked to send, forwarded ones excluded.
+        output_requests: u64 = "Output Requests", ["Ip6.OutRequests"], "packets";
+        /// Routing entries dropped although they were valid.
+        routing_discards: u64 = "Routing Discards", [], "packets";
+        /// Valid outgoing datagrams dropped, e.g. for lack of buffer space.
+        discarded_output_packets: u64 = "Discarded Output Packets", ["Ip6.OutDiscards"], "packets";
+        /// Outgoing datagrams dropped because no route matched.
+        output_packet_no_route: u64 = "Output Packet No Route", ["Ip6.OutNoRoutes"], "packets";
+        /// Fragments received that needed reassembly.
+        reassembly_required: u64 = "Reassembly Required", ["Ip6.ReasmReqds"], "fragments";
+        /// Datagrams reassembled from fragments.
+        reassembly_successful: u64 = "Reassembly Successful", ["Ip6.ReasmOKs"], "packets";
+        /// Reassemblies that failed, e.g. on a timeout.
+        reassembly_failures: u64 = "Reassembly Failures", ["Ip6.ReasmFails"], "packets";
+        /// Datagrams split into fragments.
+        datagrams_successfully_fragmented: u64 = "Datagrams Successfully Fragmented", ["Ip6.FragOKs"], "packets";
+        /// Datagrams that needed fragmenting but could not be.
+        datagrams_failing_fragmentation: u64 = "Datagrams Failing Fragmentation", ["Ip6.FragFails"], "packets";
+        /// Fragments generated by fragmentation.
+        fragments_created: u64 = "Fragments Created", ["Ip6.FragCreates"], "fragments";
+    }
+}
+
+stats_struct! {
+    IcmpV4Statistics, "ICMPv4 Statistics" {
+        /// All ICMP messages, including the ones in error.
+        messages: NetworkData = "Messages", ["Icmp.InMsgs", "Icmp.OutMsgs"], "messages";
+        /// Messages with ICMP specific errors such as a bad checksum.
+        errors: NetworkData = "Errors", ["Icmp.InErrors", "Icmp.OutErrors"], "messages";
+        /// Host, network, port or protocol unreachable.
+        destination_unreachable: NetworkData = "Destination Unreachable", ["Icmp.InDestUnreachs", "Icmp.OutDestUnreachs"], "messages";
+        /// TTL or reassembly time exceeded, as seen by traceroute.
+        time_exceeded: NetworkData = "Time Exceeded", ["Icmp.InTimeExcds", "Icmp.OutTimeExcds"], "messages";
+        /// Datagrams with a header problem.
+        parameter_problems: NetworkData = "Parameter Problems", ["Icmp.InParmProbs", "Icmp.OutParmProbs"], "messages";
+        /// Requests to slow down, obsolete.
+        source_quenches: NetworkData = "Source Quenches", ["Icmp.InSrcQuenchs", "Icmp.OutSrcQuenchs"], "messages";
+        /// Better routes announced by a router.
+        redirects: NetworkData = "Redirects", ["Icmp.InRedirects", "Icmp.OutRedirects"], "messages";
+        /// Ping replies.
+        echo_replies: NetworkData = "Echo Replies", ["Icmp.InEchoReps", "Icmp.OutEchoReps"], "messages";
+        /// Ping requests.
+        echos: NetworkData = "Echos", ["Icmp.InEchos", "Icmp.OutEchos"], "messages";
+        /// Timestamp requests.
+        timestamps: NetworkData = "Timestamps", ["Icmp.InTimestamps", "Icmp.OutTimestamps"], "messages";
+        /// Timestamp replies.
+        timestamp_replies: NetworkData = "Timestamp Replies", ["Icmp.InTimestampReps", "Icmp.OutTimestampReps"], "messages";
+        /// Address mask requests.
+        address_masks: NetworkData = "Address Masks", ["Icmp.InAddrMasks", "Icmp.OutAddrMasks"], "messages";
+        /// Address mask replies.
+        address_mask_replies: NetworkData = "Address Mask Replies", ["Icmp.InAddrMaskReps", "Icmp.OutAddrMaskReps"], "messages";
+        /// Hosts looking for a router.
+        router_solicitations: NetworkData = "Router Solicitations", [], "messages";
+        /// Routers announcing themselves.
+        router_advertisements: NetworkData = "Router Advertisements", [], "messages";
+    }
+}
+
+stats_struct! {
+    IcmpV6Statistics, "ICMPv6 Statistics" {
+        /// All ICMPv6 messages, including the ones in error.
+        messages: NetworkData = "Messages", ["Icmp6.InMsgs", "Icmp6.OutMsgs"], "messages";
+        /// Messages with ICMPv6 specific errors such as a bad checksum.
+        errors: NetworkData = "Errors", ["Icmp6.InErrors", "Icmp6.OutErrors"], "messages";
+        /// Address, port or route unreachable.
+        destination_unreachable: NetworkData = "Destination Unreachable", ["Icmp6.InDestUnreachs", "Icmp6.OutDestUnreachs"], "messages";
+        /// A packet exceeded the path MTU, used for path MTU discovery.
+        packet_too_big: NetworkData = "Packet Too Big", ["Icmp6.InPktTooBigs", "Icmp6.OutPktTooBigs"], "messages";
+        /// Hop limit or reassembly time exceeded, as seen by traceroute.
+        time_exceeded: NetworkData = "Time Exceeded", ["Icmp6.InTimeExcds", "Icmp6.OutTimeExcds"], "messages";
+        /// Datagrams with a header problem.
+        parameter_problems: NetworkData = "Parameter Problems", ["Icmp6.InParmProblems", "Icmp6.OutParmProblems"], "messages";
+        /// Ping requests.
+        echos: NetworkData = "Echos", ["Icmp6.InEchos", "Icmp6.OutEchos"], "messages";
+        /// Ping replies.
+        echo_replies: NetworkData = "Echo Replies", ["Icmp6.InEchoReplies", "Icmp6.OutEchoReplies"], "messages";
+        /// Multicast listener queries.
+        mld_queries: NetworkData = "MLD Queries", ["Icmp6.InGroupMembQueries", "Icmp6.OutGroupMembQueries"], "messages";
+        /// Multicast listener reports.
+        mld_reports: NetworkData = "MLD Reports", ["Icmp6.InGroupMembResponses", "Icmp6.OutGroupMembResponses"], "messages";
+        /// Multicast listeners leaving a group.
+        mld_dones: NetworkData = "MLD Dones", ["Icmp6.InGroupMembReductions", "Icmp6.OutGroupMembReductions"], "messages";
+        /// Hosts looking for a router.
+        router_solicitations: NetworkData = "Router Solicitations", ["Icmp6.InRouterSolicits", "Icmp6.OutRouterSolicits"], "messages";
+        /// Routers announcing themselves and their prefixes.
+        router_advertisements: NetworkData = "Router Advertisements", ["Icmp6.InRouterAdvertisements", "Icmp6.OutRouterAdvertisements"], "messages";
+        /// Neighbor discovery requests, the IPv6 ARP.
+        neighbor_solicitations: NetworkData = "Neighbor Solicitations", ["Icmp6.InNeighborSolicits", "Icmp6.OutNeighborSolicits"], "messages";
+        /// Neighbor discovery replies.
+        neighbor_advertisements: NetworkData = "Neighbor Advertisements", ["Icmp6.InNeighborAdvertisements", "Icmp6.OutNeighborAdvertisements"], "messages";
+        /// Better routes announced by a router.
+        redirects: NetworkData = "Redirects", ["Icmp6.InRedirects", "Icmp6.OutRedirects"], "messages";
+        /// Router renumbering messages.
+        router_renumberings: NetworkData = "Router Renumberings", [], "messages";
+    }
+}
+
+stats_struct! {
+    TcpV4Statistics, "TCP Statistics for IPv4" {
+        /// Connections opened by this host (SYN-SENT).
+        active_opens: u64 = "Active Opens", ["Tcp.ActiveOpens"], "connections";
+        /// Connections accepted from other hosts (SYN-RCVD).
+        passive_opens: u64 = "Passive Opens", ["Tcp.PassiveOpens"], "connections";
+        /// Connections that failed before being established.
+        failed_connection_attempts: u64 = "Failed Connection Attempts", ["Tcp.AttemptFails"], "connections";
+        /// Established connections that were reset.
+        reset_connections: u64 = "Reset Connections", ["Tcp.EstabResets"], "connections";
+        /// Connections currently established or closing.
+        current_connections: u64 = "Current Connections", ["Tcp.CurrEstab"], "connections";
+        /// Segments received, including the ones in error.
+        segments_received: u64 = "Segments Received", ["Tcp.InSegs"], "segments";
+        /// Segments sent, retransmissions excluded.
+        segments_sent: u64 = "Segments Sent", ["Tcp.OutSegs"], "segments";
+        /// Segments sent again because they were not acknowledged.
+        segments_retransmitted: u64 = "Segments Retransmitted", ["Tcp.RetransSegs"], "segments";
+    }
+}
+
+// Linux counts TCP over IPv6 in the same `Tcp` counters as IPv4.
+stats_struct! {
+    TcpV6Statistics, "TCP Statistics for IPv6" {
+        /// Connections opened by this host (SYN-SENT).
+        active_opens: u64 = "Active Opens", [], "connections";
+        /// Connections accepted from other hosts (SYN-RCVD).
+        passive_opens: u64 = "Passive Opens", [], "connections";
+        /// Connections that failed before being established.
+        failed_connection_attempts: u64 = "Failed Connection Attempts", [], "connections";
+        /// Established connections that were reset.
+        reset_connections: u64 = "Reset Connections", [], "connections";
+        /// Connections currently established or closing.
+        current_connections: u64 = "Current Connections", [], "connections";
+        /// Segments received, including the ones in error.
+        segments_received: u64 = "Segments Received", [], "segments";
+        /// Segments sent, retransmissions excluded.
+        segments_sent: u64 = "Segments Sent", [], "segments";
+        /// Segments sent again because they were not acknowledged.
+        segments_retransmitted: u64 = "Segments Retransmitted", [], "segments";
+    }
+}
+
+stats_struct! {
+    UdpV4Statistics, "UDP Statistics for IPv4" {
+        /// Datagrams handed to an application.
+        datagrams_received: u64 = "Datagrams Received", ["Udp.InDatagrams"], "datagrams";
+        /// Datagrams for a port nothing listens on.
+        no_ports: u64 = "No Ports", ["Udp.NoPorts"], "datagrams";
+        /// Datagrams dropped for other reasons, e.g. a full receive buffer.
+        receive_errors: u64 = "Receive Errors", ["Udp.InErrors"], "datagrams";
+        /// Datagrams sent.
+        datagrams_sent: u64 = "Datagrams Sent", ["Udp.OutDatagrams"], "datagrams";
+    }
+}
+
+stats_struct! {
+    UdpV6Statistics, "UDP Statistics for IPv6" {
+        /// Datagrams handed to an application.
+        datagrams_received: u64 = "Datagrams Received", ["Udp6.InDatagrams"], "datagrams";
+        /// Datagrams for a port nothing listens on.
+        no_ports: u64 = "No Ports", ["Udp6.NoPorts"], "datagrams";
+        /// Datagrams dropped for other reasons, e.g. a full receive buffer.
+        receive_errors: u64 = "Receive Errors", ["Udp6.InErrors"], "datagrams";
+        /// Datagrams sent.
+        datagrams_sent: u64 = "Datagrams Sent", ["Udp6.OutDatagrams"], "datagrams";
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
 pub struct NetworkStatistics {
     interface: InterfaceStats,
     ipv4: IpV4Statistics,
@@ -151,987 +383,639 @@ pub struct NetworkStatistics {
     tcp_ipv6: TcpV6Statistics,
     udp_ipv4: UdpV4Statistics,
     udp_ipv6: UdpV6Statistics,
+    /// Linux only, see `parse_proc_net_netstat`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extended: Option<ExtendedStatistics>,
+}
+
+/// Counters from `/proc/net/netstat` by section (`TcpExt`, `IpExt`, ...) and
+/// name. Which counters exist depends on the kernel, so they are kept as
+/// they come instead of in a fixed struct.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ExtendedStatistics(pub BTreeMap<String, BTreeMap<String, u64>>);
+
+impl ExtendedStatistics {
+    pub fn get(&self, section: &str, name: &str) -> Option<u64> {
+        self.0.get(section)?.get(name).copied()
+    }
+}
+
+/// A counter of a `StatsSnapshot`: a plain count, or a received / sent pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum StatValue {
+    Count(u64),
+    Pair(NetworkData),
+}
+
+/// One section of a `StatsSnapshot`, with its counters in the order the
+/// source lists them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsSection {
+    /// `snake_case`, e.g. `tcp_ipv4` for "TCP Statistics for IPv4".
+    pub name: String,
+    pub entries: Vec<(String, StatValue)>,
 }
 
-trait DisplayStats {
-    fn display(&self);
+impl StatsSection {
+    pub fn get(&self, key: &str) -> Option<StatValue> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// Adds a counter, or replaces the value of one that is already there.
+    pub fn insert(&mut self, key: String, value: StatValue) {
+        match self.entries.iter_mut().find(|(name, _)| *name == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((key, value)),
+        }
+    }
 }
 
-impl DisplayStats for NetworkStatistics {
-    fn display(&self) {
-        self.interface.display();
-        self.ipv4.display();
-        self.ipv6.display();
-        self.icmpv4.display();
-        self.icmpv6.display();
-        self.tcp_ipv4.display();
-        self.tcp_ipv6.display();
-        self.udp_ipv4.display();
-        self.udp_ipv6.display();
+impl Serialize for StatsSection {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.entries.iter().map(|(key, value)| (key, value)))
     }
 }
 
-impl DisplayStats for InterfaceStats {
-    fn display(&self) {
-        println!("Interface Statistics");
-        println!("\tReceived: {:?}", self.bytes);
-        println!("\tSent: {:?}", self.bytes);
-        println!("\tUnicast packets: {:?}", self.unicast_packets);
-        println!(
-            "\tNon-unicast packets: {}",
-            self.non_unicast_packets.received
-        );
-        println!("\tDiscards: {:?}", self.discards);
-        println!("\tErrors: {:?}", self.errors);
-        println!("\tUnknown protocols: {}", self.unknown_protocols);
+/// Statistics as sections of named counters, without a fixed layout. Any
+/// source can produce one, `NetworkStatistics` is derived from it and it
+/// serializes to the same shape, plus whatever counters the typed structs
+/// do not know about.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    pub sections: Vec<StatsSection>,
+}
+
+impl StatsSnapshot {
+    pub fn section(&self, name: &str) -> Option<&StatsSection> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    /// The section called `name`, added at the end if it does not exist yet.
+    pub fn section_mut(&mut self, name: &str) -> &mut StatsSection {
+        let index = match self
+            .sections
+            .iter()
+            .position(|section| section.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.sections.push(StatsSection {
+                    name: name.to_string(),
+                    entries: Vec::new(),
+                });
+                self.sections.len() - 1
+            }
+        };
+        &mut self.sections[index]
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<StatValue> {
+        self.section(section)?.get(key)
+    }
+
+    /// Reads one section into its typed struct, missing counters stay at
+    /// zero.
+    fn typed_section<T: StatsStruct>(&self) -> T {
+        self.section(&section_name(T::TITLE))
+            .map(T::from_section)
+            .unwrap_or_default()
     }
 }
 
-impl DisplayStats for IpV4Statistics {
-    fn display(&self) {
-        println!("IPv4 Statistics");
-        println!("\tPackets received: {}", self.packets_received);
-        println!("\tReceived header errors: {}", self.received_header_errors);
-        println!(
-            "\tReceived address errors: {}",
-            self.received_address_errors
-        );
-        println!("\tDatagrams forwarded: {}", self.datagrams_forwarded);
-        println!(
-            "\tUnknown protocols received: {}",
-            self.unknown_protocols_received
-        );
-        println!(
-            "\tReceived packets discarded: {}",
-            self.received_packets_discarded
-        );
-        println!(
-            "\tReceived packets delivered: {}",
-            self.received_packets_delivered
-        );
-        println!("\tOutput requests: {}", self.output_requests);
-        println!("\tRouting discards: {}", self.routing_discards);
-        println!(
-            "\tDiscarded output packets: {}",
-            self.discarded_output_packets
-        );
-        println!("\tOutput packet no route: {}", self.output_packet_no_route);
-        println!("\tReassembly required: {}", self.reassembly_required);
-        println!("\tReassembly successful: {}", self.reassembly_successful);
-        println!("\tReassembly failures: {}", self.reassembly_failures);
-        println!(
-            "\tDatagrams successfully fragmented: {}",
-            self.datagrams_successfully_fragmented
-        );
-        println!(
-            "\tDatagrams failing fragmentation: {}",
-            self.datagrams_failing_fragmentation
-        );
-        println!("\tFragments created: {}", self.fragments_created);
+impl Serialize for StatsSnapshot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.sections.iter().map(|section| (&section.name, section)))
     }
 }
 
-impl DisplayStats for IpV6Statistics {
-    fn display(&self) {
-        println!("IPv6 Statistics");
-        println!("\tPackets received: {}", self.packets_received);
-        println!("\tReceived header errors: {}", self.received_header_errors);
-        println!(
-            "\tReceived address errors: {}",
-            self.received_address_errors
-        );
-        println!("\tDatagrams forwarded: {}", self.datagrams_forwarded);
-        println!(
-            "\tUnknown protocols received: {}",
-            self.unknown_protocols_received
-        );
-        println!(
-            "\tReceived packets discarded: {}",
-            self.received_packets_discarded
-        );
-        println!(
-            "\tReceived packets delivered: {}",
-            self.received_packets_delivered
-        );
-        println!("\tOutput requests: {}", self.output_requests);
-        println!("\tRouting discards: {}", self.routing_discards);
-        println!(
-            "\tDiscarded output packets: {}",
-            self.discarded_output_packets
-        );
-        println!("\tOutput packet no route: {}", self.output_packet_no_route);
-        println!("\tReassembly required: {}", self.reassembly_required);
-        println!("\tReassembly successful: {}", self.reassembly_successful);
-        println!("\tReassembly failures: {}", self.reassembly_failures);
-        println!(
-            "\tDatagrams successfully fragmented: {}",
-            self.datagrams_successfully_fragmented
-        );
-        println!(
-            "\tDatagrams failing fragmentation: {}",
-            self.datagrams_failing_fragmentation
-        );
-        println!("\tFragments created: {}", self.fragments_created);
+impl From<&StatsSnapshot> for NetworkStatistics {
+    fn from(snapshot: &StatsSnapshot) -> Self {
+        NetworkStatistics {
+            interface: snapshot.typed_section(),
+            ipv4: snapshot.typed_section(),
+            ipv6: snapshot.typed_section(),
+            icmpv4: snapshot.typed_section(),
+            icmpv6: snapshot.typed_section(),
+            tcp_ipv4: snapshot.typed_section(),
+            tcp_ipv6: snapshot.typed_section(),
+            udp_ipv4: snapshot.typed_section(),
+            udp_ipv6: snapshot.typed_section(),
+            extended: None,
+        }
     }
 }
 
-impl DisplayStats for IcmpV4Statistics {
-    fn display(&self) {
-        println!("ICMPv4 Statistics");
-        println!("\tMessages: {:?}", self.messages);
-        println!("\tErrors: {:?}", self.errors);
-        println!(
-            "\tDestination unreachable: {:?}",
-            self.destination_unreachable
-        );
-        println!("\tTime exceeded: {:?}", self.time_exceeded);
-        println!("\tParameter problems: {:?}", self.parameter_problems);
-        println!("\tSource quenches: {:?}", self.source_quenches);
-        println!("\tRedirects: {:?}", self.redirects);
-        println!("\tEcho replies: {:?}", self.echo_replies);
-        println!("\tEchos: {:?}", self.echos);
-        println!("\tTimestamps: {:?}", self.timestamps);
-        println!("\tTimestamp replies: {:?}", self.timestamp_replies);
-        println!("\tAddress masks: {:?}", self.address_masks);
-        println!("\tAddress mask replies: {:?}", self.address_mask_replies);
-        println!("\tRouter solicitations: {:?}", self.router_solicitations);
-        println!("\tRouter advertisements: {:?}", self.router_advertisements);
-    }
-}
-
-impl DisplayStats for IcmpV6Statistics {
-    fn display(&self) {
-        println!("ICMPv6 Statistics");
-        println!("\tMessages: {:?}", self.messages);
-        println!("\tErrors: {:?}", self.errors);
-        println!(
-            "\tDestination unreachable: {:?}",
-            self.destination_unreachable
-        );
-        println!("\tPacket too big: {:?}", self.packet_too_big);
-        println!("\tTime exceeded: {:?}", self.time_exceeded);
-        println!("\tParameter problems: {:?}", self.parameter_problems);
-        println!("\tEchos: {:?}", self.echos);
-        println!("\tEcho replies: {:?}", self.echo_replies);
-        println!("\tMLD queries: {:?}", self.mld_queries);
-        println!("\tMLD reports: {:?}", self.mld_reports);
-        println!("\tMLD dones: {:?}", self.mld_dones);
-        println!("\tRouter solicitations: {:?}", self.router_solicitations);
-        println!("\tRouter advertisements: {:?}", self.router_advertisements);
-        println!(
-            "\tNeighbor solicitations: {:?}",
-            self.neighbor_solicitations
-        );
-        println!(
-            "\tNeighbor advertisements: {:?}",
-            self.neighbor_advertisements
-        );
-        println!("\tRedirects: {:?}", self.redirects);
-        println!("\tRouter renumberings: {:?}", self.router_renumberings);
+impl NetworkStatistics {
+    pub fn set_extended(&mut self, extended: ExtendedStatistics) {
+        self.extended = Some(extended);
     }
 }
 
-impl DisplayStats for TcpV4Statistics {
-    fn display(&self) {
-        println!("TCP Statistics for IPv4");
-        println!("\tActive opens: {}", self.active_opens);
-        println!("\tPassive opens: {}", self.passive_opens);
-        println!(
-            "\tFailed connection attempts: {}",
-            self.failed_connection_attempts
-        );
-        println!("\tReset connections: {}", self.reset_connections);
-        println!("\tCurrent connections: {}", self.current_connections);
-        println!("\tSegments received: {}", self.segments_received);
-        println!("\tSegments sent: {}", self.segments_sent);
-        println!("\tSegments retransmitted: {}", self.segments_retransmitted);
-    }
-}
-
-impl DisplayStats for TcpV6Statistics {
-    fn display(&self) {
-        println!("TCP Statistics for IPv6");
-        println!("\tActive opens: {}", self.active_opens);
-        println!("\tPassive opens: {}", self.passive_opens);
-        println!(
-            "\tFailed connection attempts: {}",
-            self.failed_connection_attempts
-        );
-        println!("\tReset connections: {}", self.reset_connections);
-        println!("\tCurrent connections: {}", self.current_connections);
-        println!("\tSegments received: {}", self.segments_received);
-        println!("\tSegments sent: {}", self.segments_sent);
-        println!("\tSegments retransmitted: {}", self.segments_retransmitted);
+/// `Packets Received` -> `packets_received`, `Non-unicast` -> `non_unicast`.
+fn stats_key<'a>(words: impl IntoIterator<Item = &'a str>) -> String {
+    words
+        .into_iter()
+        .map(|word| word.to_lowercase().replace('-', "_"))
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Section name for a `netstat -s` header, e.g. `tcp_ipv4` for
+/// "TCP Statistics for IPv4".
+fn section_name(header: &str) -> String {
+    stats_key(
+        header
+            .split_whitespace()
+            .filter(|word| !matches!(*word, "Statistics" | "for")),
+    )
+}
+
+/// `section_name` of a header line, `None` for anything that is not one.
+fn stats_section_name(line: &str) -> Option<String> {
+    let is_header = line.contains("Statistics")
+        && !line.contains('=')
+        && !line
+            .split_whitespace()
+            .any(|token| token.parse::<u64>().is_ok());
+    is_header.then(|| section_name(line))
+}
+
+/// Every `stats_struct!` of `NetworkStatistics`, as (netstat header, fields).
+pub const STATS_SECTIONS: &[(&str, &[StatField])] = &[
+    (InterfaceStats::TITLE, InterfaceStats::FIELDS),
+    (IpV4Statistics::TITLE, IpV4Statistics::FIELDS),
+    (IpV6Statistics::TITLE, IpV6Statistics::FIELDS),
+    (IcmpV4Statistics::TITLE, IcmpV4Statistics::FIELDS),
+    (IcmpV6Statistics::TITLE, IcmpV6Statistics::FIELDS),
+    (TcpV4Statistics::TITLE, TcpV4Statistics::FIELDS),
+    (TcpV6Statistics::TITLE, TcpV6Statistics::FIELDS),
+    (UdpV4Statistics::TITLE, UdpV4Statistics::FIELDS),
+    (UdpV6Statistics::TITLE, UdpV6Statistics::FIELDS),
+];
+
+/// The fields of `STATS_SECTIONS` by section and snapshot key, as sent to
+/// the UI for units and descriptions.
+pub fn stat_fields() -> BTreeMap<String, BTreeMap<String, StatField>> {
+    STATS_SECTIONS
+        .iter()
+        .map(|(title, fields)| {
+            let fields = fields
+                .iter()
+                .map(|field| (stats_key(field.label.split_whitespace()), *field))
+                .collect();
+            (section_name(title), fields)
+        })
+        .collect()
+}
+
+/// Builds the snapshot `netstat -s` would print from the Linux SNMP
+/// counters (`parse_proc_net_netstat` of `/proc/net/snmp`, merged with
+/// `parse_proc_net_snmp6`). Counters Linux does not keep are left out.
+pub fn snmp_snapshot(snmp: &ExtendedStatistics) -> StatsSnapshot {
+    let lookup = |key: &str| {
+        let (section, name) = key.split_once('.')?;
+        snmp.get(section, name)
+    };
+
+    let mut snapshot = StatsSnapshot::default();
+    for (title, fields) in STATS_SECTIONS {
+        let entries: Vec<(String, StatValue)> = fields
+            .iter()
+            .filter_map(|field| {
+                let value = match field.snmp {
+                    [key] => StatValue::Count(lookup(key)?),
+                    [received, sent] => StatValue::Pair(NetworkData {
+                        received: lookup(received)?,
+                        sent: lookup(sent)?,
+                    }),
+                    _ => return None,
+                };
+                Some((stats_key(field.label.split_whitespace()), value))
+            })
+            .collect();
+
+        if !entries.is_empty() {
+            snapshot.sections.push(StatsSection {
+                name: section_name(title),
+                entries,
+            });
+        }
     }
+    snapshot
 }
 
-impl DisplayStats for UdpV4Statistics {
-    fn display(&self) {
-        println!("UDP Statistics for IPv4");
-        println!("\tDatagrams received: {}", self.datagrams_received);
-        println!("\tNo ports: {}", self.no_ports);
-        println!("\tReceive errors: {}", self.receive_errors);
-        println!("\tDatagrams sent: {}", self.datagrams_sent);
+/// The `interface` section of `netstat -e`, summed over the adapters of
+/// `parse_proc_net_dev`. Linux does not count unicast and non-unicast
+/// packets apart, nor unknown protocols, so those are left out.
+fn interface_section(interfaces: &[NetworkInterface]) -> StatsSection {
+    let total = |counter: fn(&NetworkInterface) -> NetworkData| {
+        let pair = interfaces
+            .iter()
+            .map(counter)
+            .fold(NetworkData::default(), |total, data| NetworkData {
+                received: total.received + data.received,
+                sent: total.sent + data.sent,
+            });
+        StatValue::Pair(pair)
+    };
+
+    StatsSection {
+        name: section_name(InterfaceStats::TITLE),
+        entries: vec![
+            ("bytes".to_string(), total(|interface| interface.bytes)),
+            ("discards".to_string(), total(|interface| interface.drops)),
+            ("errors".to_string(), total(|interface| interface.errors)),
+        ],
     }
 }
 
-impl DisplayStats for UdpV6Statistics {
-    fn display(&self) {
-        println!("UDP Statistics for IPv6");
-        println!("\tDatagrams received: {}", self.datagrams_received);
-        println!("\tNo ports: {}", self.no_ports);
-        println!("\tReceive errors: {}", self.receive_errors);
-        println!("\tDatagrams sent: {}", self.datagrams_sent);
+/// `src/net-stat.ts`, the TypeScript type of a serialized `StatsSnapshot`.
+/// Sections and counters without a `stats_struct!` field are still allowed.
+pub fn typescript_definitions() -> String {
+    let mut ts = String::from(
+        "// Generated from the `stats_struct!` definitions in src-tauri/src/lib.rs\n\
+         // by `typescript_definitions`, do not edit.\n\
+         \n\
+         export type NetStatValue = {\n  received: number;\n  sent: number;\n};\n\
+         \n\
+         export type NetStat = {\n",
+    );
+    for (title, fields) in STATS_SECTIONS {
+        ts.push_str(&format!("  {}: {{\n", section_name(title)));
+        for field in *fields {
+            ts.push_str(&format!(
+                "    /** {} */\n    {}: {};\n",
+                field.description,
+                stats_key(field.label.split_whitespace()),
+                if field.pair { "NetStatValue" } else { "number" }
+            ));
+        }
+        ts.push_str("    [counter: string]: NetStatValue | number;\n  };\n");
     }
+    ts.push_str("};\n");
+    ts
 }
 
-impl NetworkStatistics {
-    fn new() -> Self {
-        NetworkStatistics {
-            interface: InterfaceStats {
-                bytes: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                unicast_packets: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                non_unicast_packets: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                discards: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                errors: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                unknown_protocols: 0,
-            },
-            ipv4: IpV4Statistics {
-                packets_received: 0,
-                received_header_errors: 0,
-                received_address_errors: 0,
-                datagrams_forwarded: 0,
-                unknown_protocols_received: 0,
-                received_packets_discarded: 0,
-                received_packets_delivered: 0,
-                output_requests: 0,
-                routing_discards: 0,
-                discarded_output_packets: 0,
-                output_packet_no_route: 0,
-                reassembly_required: 0,
-                reassembly_successful: 0,
-                reassembly_failures: 0,
-                datagrams_successfully_fragmented: 0,
-                datagrams_failing_fragmentation: 0,
-                fragments_created: 0,
-            },
-            ipv6: IpV6Statistics {
-                packets_received: 0,
-                received_header_errors: 0,
-                received_address_errors: 0,
-                datagrams_forwarded: 0,
-                unknown_protocols_received: 0,
-                received_packets_discarded: 0,
-                received_packets_delivered: 0,
-                output_requests: 0,
-                routing_discards: 0,
-                discarded_output_packets: 0,
-                output_packet_no_route: 0,
-                reassembly_required: 0,
-                reassembly_successful: 0,
-                reassembly_failures: 0,
-                datagrams_successfully_fragmented: 0,
-                datagrams_failing_fragmentation: 0,
-                fragments_created: 0,
-            },
-            icmpv4: IcmpV4Statistics {
-                messages: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                errors: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                destination_unreachable: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                time_exceeded: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                parameter_problems: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                source_quenches: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                redirects: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                echo_replies: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                echos: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                timestamps: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                timestamp_replies: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                address_masks: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                address_mask_replies: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                router_solicitations: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                router_advertisements: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-            },
-            icmpv6: IcmpV6Statistics {
-                messages: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                errors: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                destination_unreachable: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                packet_too_big: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                time_exceeded: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                parameter_problems: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                echos: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                echo_replies: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                mld_queries: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                mld_reports: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                mld_dones: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                router_solicitations: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                router_advertisements: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                neighbor_solicitations: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                neighbor_advertisements: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                redirects: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-                router_renumberings: NetworkData {
-                    received: 0,
-                    sent: 0,
-                },
-            },
-            tcp_ipv4: TcpV4Statistics {
-                active_opens: 0,
-                passive_opens: 0,
-                failed_connection_attempts: 0,
-                reset_connections: 0,
-                current_connections: 0,
-                segments_received: 0,
-                segments_sent: 0,
-                segments_retransmitted: 0,
-            },
-            tcp_ipv6: TcpV6Statistics {
-                active_opens: 0,
-                passive_opens: 0,
-                failed_connection_attempts: 0,
-                reset_connections: 0,
-                current_connections: 0,
-                segments_received: 0,
-                segments_sent: 0,
-                segments_retransmitted: 0,
-            },
-            udp_ipv4: UdpV4Statistics {
-                datagrams_received: 0,
-                no_ports: 0,
-                receive_errors: 0,
-                datagrams_sent: 0,
-            },
-            udp_ipv6: UdpV6Statistics {
-                datagrams_received: 0,
-                no_ports: 0,
-                receive_errors: 0,
-                datagrams_sent: 0,
-            },
+/// Adds one counter line to `section`. Both layouts of `netstat -s` are
+/// read: `Name = value` and `Name  received  sent`. The per type ICMP rows
+/// of newer Windows builds (`Type 3  12  4`, or `InType3 = 12` and
+/// `OutType3 = 4`) all end up as `type_3` pairs.
+fn parse_stats_line(tokens: &[&str], section: &mut StatsSection) {
+    if let Some(split) = tokens.iter().position(|token| *token == "=") {
+        let (name, value) = (&tokens[..split], &tokens[split + 1..]);
+        let ([first, ..], [value]) = (name, value) else {
+            return;
+        };
+        let Ok(value) = value.parse() else {
+            return;
+        };
+
+        let icmp_type = match first.strip_prefix("InType") {
+            Some(icmp_type) => Some((icmp_type, false)),
+            None => first
+                .strip_prefix("OutType")
+                .map(|icmp_type| (icmp_type, true)),
+        };
+        if let Some((icmp_type, sent)) = icmp_type.filter(|_| name.len() == 1) {
+            let key = format!("type_{}", icmp_type);
+            let mut pair = match section.get(&key) {
+                Some(StatValue::Pair(pair)) => pair,
+                _ => NetworkData::default(),
+            };
+            if sent {
+                pair.sent = value;
+            } else {
+                pair.received = value;
+            }
+            section.insert(key, StatValue::Pair(pair));
+            return;
         }
+
+        section.insert(stats_key(name.iter().copied()), StatValue::Count(value));
+        return;
+    }
+
+    let numbers = tokens
+        .iter()
+        .rev()
+        .take_while(|token| token.parse::<u64>().is_ok())
+        .count()
+        .min(2);
+    let (name, values) = tokens.split_at(tokens.len() - numbers);
+    if name.is_empty() {
+        return;
+    }
+    let name = stats_key(name.iter().copied());
+    match values {
+        [value] => section.insert(name, StatValue::Count(value.parse().unwrap())),
+        [received, sent] => section.insert(
+            name,
+            StatValue::Pair(NetworkData {
+                received: received.parse().unwrap(),
+                sent: sent.parse().unwrap(),
+            }),
+        ),
+        _ => (),
     }
 }
 
-pub fn parse_stats(input: &str) -> NetworkStatistics {
-    let mut stats = NetworkStatistics::new();
+/// Parses `netstat -s`, with or without `-e` and `-p <protocol>`, keeping
+/// every section and counter it finds.
+pub fn parse_stats_snapshot(input: &str) -> StatsSnapshot {
+    let mut snapshot = StatsSnapshot::default();
 
-    let mut current_section = "";
+    let mut current_section: Option<String> = None;
     let lines = input
         .lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty());
 
     for line in lines {
-        let tokens: Vec<&str> = line.split_whitespace().collect();
+        // `netstat -s -p <protocol>` lists the connections after the counters
+        if line.starts_with("Active Connections") {
+            current_section = None;
+            continue;
+        }
+        if let Some(name) = stats_section_name(line) {
+            snapshot.section_mut(&name);
+            current_section = Some(name);
+            continue;
+        }
 
-        match tokens.as_slice() {
-            ["Bytes", received, sent] => {
-                stats.interface.bytes.received = received.parse().unwrap();
-                stats.interface.bytes.sent = sent.parse().unwrap();
-            }
-            ["Unicast", "packets", received, sent] => {
-                stats.interface.unicast_packets.received = received.parse().unwrap();
-                stats.interface.unicast_packets.sent = sent.parse().unwrap();
-            }
-            ["Non-unicast", "packets", received, sent] => {
-                stats.interface.non_unicast_packets.received = received.parse().unwrap();
-                stats.interface.non_unicast_packets.sent = sent.parse().unwrap();
-            }
-            ["Discards", received, sent] => {
-                stats.interface.discards.received = received.parse().unwrap();
-                stats.interface.discards.sent = sent.parse().unwrap();
-            }
-            ["Errors", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.errors.received = received.parse().unwrap();
-                    stats.icmpv4.errors.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.errors.received = received.parse().unwrap();
-                    stats.icmpv6.errors.sent = sent.parse().unwrap();
-                }
-                _ => {
-                    stats.interface.errors.received = received.parse().unwrap();
-                    stats.interface.errors.sent = sent.parse().unwrap();
-                }
-            },
-            ["Unknown", "protocols", unknown_protocols] => {
-                stats.interface.unknown_protocols = unknown_protocols.parse().unwrap();
-            }
-            ["Packets", "Received", "=", packets_received] => match current_section {
-                "IPv4" => stats.ipv4.packets_received = packets_received.parse().unwrap(),
-                "IPv6" => stats.ipv6.packets_received = packets_received.parse().unwrap(),
-                _ => (),
-            },
-            ["Received", "Header", "Errors", "=", received_header_errors] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.received_header_errors = received_header_errors.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.received_header_errors = received_header_errors.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Received", "Address", "Errors", "=", received_address_errors] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.received_address_errors =
-                            received_address_errors.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.received_address_errors =
-                            received_address_errors.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Datagrams", "Forwarded", "=", datagrams_forwarded] => match current_section {
-                "IPv4" => stats.ipv4.datagrams_forwarded = datagrams_forwarded.parse().unwrap(),
-                "IPv6" => stats.ipv6.datagrams_forwarded = datagrams_forwarded.parse().unwrap(),
-                _ => (),
-            },
-            ["Unknown", "Protocols", "Received", "=", unknown_protocols_received] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.unknown_protocols_received =
-                            unknown_protocols_received.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.unknown_protocols_received =
-                            unknown_protocols_received.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Received", "Packets", "Discarded", "=", received_packets_discarded] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.received_packets_discarded =
-                            received_packets_discarded.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.received_packets_discarded =
-                            received_packets_discarded.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Received", "Packets", "Delivered", "=", received_packets_delivered] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.received_packets_delivered =
-                            received_packets_delivered.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.received_packets_delivered =
-                            received_packets_delivered.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Output", "Requests", "=", output_requests] => match current_section {
-                "IPv4" => stats.ipv4.output_requests = output_requests.parse().unwrap(),
-                "IPv6" => stats.ipv6.output_requests = output_requests.parse().unwrap(),
-                _ => (),
-            },
-            ["Routing", "Discards", "=", routing_discards] => match current_section {
-                "IPv4" => stats.ipv4.routing_discards = routing_discards.parse().unwrap(),
-                "IPv6" => stats.ipv6.routing_discards = routing_discards.parse().unwrap(),
-                _ => (),
-            },
-            ["Discarded", "Output", "Packets", "=", discarded_output_packets] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.discarded_output_packets =
-                            discarded_output_packets.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.discarded_output_packets =
-                            discarded_output_packets.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Output", "Packet", "No", "Route", "=", output_packet_no_route] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.output_packet_no_route = output_packet_no_route.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.output_packet_no_route = output_packet_no_route.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Reassembly", "Required", "=", reassembly_required] => match current_section {
-                "IPv4" => stats.ipv4.reassembly_required = reassembly_required.parse().unwrap(),
-                "IPv6" => stats.ipv6.reassembly_required = reassembly_required.parse().unwrap(),
-                _ => (),
-            },
-            ["Reassembly", "Successful", "=", reassembly_successful] => match current_section {
-                "IPv4" => stats.ipv4.reassembly_successful = reassembly_successful.parse().unwrap(),
-                "IPv6" => stats.ipv6.reassembly_successful = reassembly_successful.parse().unwrap(),
-                _ => (),
-            },
-            ["Reassembly", "Failures", "=", reassembly_failures] => match current_section {
-                "IPv4" => stats.ipv4.reassembly_failures = reassembly_failures.parse().unwrap(),
-                "IPv6" => stats.ipv6.reassembly_failures = reassembly_failures.parse().unwrap(),
-                _ => (),
-            },
-            ["Datagrams", "Successfully", "Fragmented", "=", datagrams_successfully_fragmented] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.datagrams_successfully_fragmented =
-                            datagrams_successfully_fragmented.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.datagrams_successfully_fragmented =
-                            datagrams_successfully_fragmented.parse().unwrap()
-                    }
-                    _ => (),
-                }
-            }
-            ["Datagrams", "Failing", "Fragmentation", "=", datagrams_failing_fragmentation] => {
-                match current_section {
-                    "IPv4" => {
-                        stats.ipv4.datagrams_failing_fragmentation =
-                            datagrams_failing_fragmentation.parse().unwrap()
-                    }
-                    "IPv6" => {
-                        stats.ipv6.datagrams_failing_fragmentation =
-                            datagrams_failing_fragmentation.parse().unwrap()
-                    }
-                    _ => (),
-                }
+        if let Some(name) = &current_section {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            parse_stats_line(&tokens, snapshot.section_mut(name));
+        }
+    }
+
+    snapshot
+}
+
+/// The typed view of `parse_stats_snapshot`.
+pub fn parse_stats(input: &str) -> NetworkStatistics {
+    NetworkStatistics::from(&parse_stats_snapshot(input))
+}
+
+/// Parses `/proc/net/netstat` (and `/proc/net/snmp`, which has the same
+/// layout): every section is a line of names followed by a line of values,
+/// both prefixed with the section name.
+pub fn parse_proc_net_netstat(input: &str) -> ExtendedStatistics {
+    let mut stats = ExtendedStatistics::default();
+    let mut lines = input.lines();
+
+    while let (Some(names), Some(values)) = (lines.next(), lines.next()) {
+        let (Some((section, names)), Some((value_section, values))) =
+            (names.split_once(':'), values.split_once(':'))
+        else {
+            continue;
+        };
+        if section != value_section {
+            continue;
+        }
+
+        let counters = stats.0.entry(section.to_string()).or_default();
+        for (name, value) in names.split_whitespace().zip(values.split_whitespace()) {
+            // a few counters (e.g. `Tcp: MaxConn`) are signed, -1 means "no limit"
+            if let Ok(value) = value.parse() {
+                counters.insert(name.to_string(), value);
             }
-            ["Fragments", "Created", "=", fragments_created] => match current_section {
-                "IPv4" => stats.ipv4.fragments_created = fragments_created.parse().unwrap(),
-                "IPv6" => stats.ipv6.fragments_created = fragments_created.parse().unwrap(),
-                _ => (),
-            },
-            ["Messages", messages_received, messages_sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.messages.received = messages_received.parse().unwrap();
-                    stats.icmpv4.messages.sent = messages_sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.messages.received = messages_received.parse().unwrap();
-                    stats.icmpv6.messages.sent = messages_sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Destination", "Unreachable", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.destination_unreachable.received = received.parse().unwrap();
-                    stats.icmpv4.destination_unreachable.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.destination_unreachable.received = received.parse().unwrap();
-                    stats.icmpv6.destination_unreachable.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Time", "Exceeded", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.time_exceeded.received = received.parse().unwrap();
-                    stats.icmpv4.time_exceeded.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.time_exceeded.received = received.parse().unwrap();
-                    stats.icmpv6.time_exceeded.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Parameter", "Problems", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.parameter_problems.received = received.parse().unwrap();
-                    stats.icmpv4.parameter_problems.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.parameter_problems.received = received.parse().unwrap();
-                    stats.icmpv6.parameter_problems.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Source", "Quenches", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.source_quenches.received = received.parse().unwrap();
-                    stats.icmpv4.source_quenches.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Redirects", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.redirects.received = received.parse().unwrap();
-                    stats.icmpv4.redirects.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.redirects.received = received.parse().unwrap();
-                    stats.icmpv6.redirects.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Echo", "Replies", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.echo_replies.received = received.parse().unwrap();
-                    stats.icmpv4.echo_replies.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.echo_replies.received = received.parse().unwrap();
-                    stats.icmpv6.echo_replies.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Echos", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.echos.received = received.parse().unwrap();
-                    stats.icmpv4.echos.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.echos.received = received.parse().unwrap();
-                    stats.icmpv6.echos.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Timestamps", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.timestamps.received = received.parse().unwrap();
-                    stats.icmpv4.timestamps.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Timestamps", "Replies", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.timestamps.received = received.parse().unwrap();
-                    stats.icmpv4.timestamps.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Address", "Masks", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.address_masks.received = received.parse().unwrap();
-                    stats.icmpv4.address_masks.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Address", "Masks", "Replies", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.address_mask_replies.received = received.parse().unwrap();
-                    stats.icmpv4.address_mask_replies.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["MLD", "Queries", received, sent] => match current_section {
-                "ICMPv6" => {
-                    stats.icmpv6.mld_queries.received = received.parse().unwrap();
-                    stats.icmpv6.mld_queries.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["MLD", "Reports", received, sent] => match current_section {
-                "ICMPv6" => {
-                    stats.icmpv6.mld_reports.received = received.parse().unwrap();
-                    stats.icmpv6.mld_reports.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["MLD", "Dones", received, sent] => match current_section {
-                "ICMPv6" => {
-                    stats.icmpv6.mld_dones.received = received.parse().unwrap();
-                    stats.icmpv6.mld_dones.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Router", "Solicitations", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.router_solicitations.received = received.parse().unwrap();
-                    stats.icmpv4.router_solicitations.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.router_solicitations.received = received.parse().unwrap();
-                    stats.icmpv6.router_solicitations.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Router", "Advertisements", received, sent] => match current_section {
-                "ICMPv4" => {
-                    stats.icmpv4.router_advertisements.received = received.parse().unwrap();
-                    stats.icmpv4.router_advertisements.sent = sent.parse().unwrap();
-                }
-                "ICMPv6" => {
-                    stats.icmpv6.router_advertisements.received = received.parse().unwrap();
-                    stats.icmpv6.router_advertisements.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Neighbor", "Solicitations", received, sent] => match current_section {
-                "ICMPv6" => {
-                    stats.icmpv6.neighbor_solicitations.received = received.parse().unwrap();
-                    stats.icmpv6.neighbor_solicitations.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Neighbor", "Advertisements", received, sent] => match current_section {
-                "ICMPv6" => {
-                    stats.icmpv6.neighbor_advertisements.received = received.parse().unwrap();
-                    stats.icmpv6.neighbor_advertisements.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Router", "Renumberings", received, sent] => match current_section {
-                "ICMPv6" => {
-                    stats.icmpv6.router_renumberings.received = received.parse().unwrap();
-                    stats.icmpv6.router_renumberings.sent = sent.parse().unwrap();
-                }
-                _ => (),
-            },
-            ["Active", "Opens", "=", active_opens] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.active_opens = active_opens.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.active_opens = active_opens.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Passive", "Opens", "=", passive_opens] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.passive_opens = passive_opens.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.passive_opens = passive_opens.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Failed", "Connection", "Attempts", "=", failed_connection_attempts] => {
-                match current_section {
-                    "TCP Statistics for IPv4" => {
-                        stats.tcp_ipv4.failed_connection_attempts =
-                            failed_connection_attempts.parse().unwrap()
-                    }
-                    "TCP Statistics for IPv6" => {
-                        stats.tcp_ipv6.failed_connection_attempts =
-                            failed_connection_attempts.parse().unwrap()
-                    }
-                    _ => (),
-                }
+        }
+    }
+
+    stats
+}
+
+/// Parses `/proc/net/snmp6`, one `Ip6InReceives  1234` pair per line, into
+/// the same sections `/proc/net/snmp` uses (`Ip6`, `Icmp6`, `Udp6`, ...).
+pub fn parse_proc_net_snmp6(input: &str) -> ExtendedStatistics {
+    let mut stats = ExtendedStatistics::default();
+
+    for line in input.lines() {
+        let mut tokens = line.split_whitespace();
+        let (Some(key), Some(value), None) = (tokens.next(), tokens.next(), tokens.next()) else {
+            continue;
+        };
+        let (Some((section, name)), Ok(value)) = (key.split_once('6'), value.parse()) else {
+            continue;
+        };
+        stats
+            .0
+            .entry(format!("{}6", section))
+            .or_default()
+            .insert(name.to_string(), value);
+    }
+
+    stats
+}
+
+/// The `/proc/net` files behind the statistics on Linux, in the order
+/// `procfs::stats_files` joins them.
+pub const PROC_NET_STATS_FILES: [&str; 4] = ["snmp", "snmp6", "dev", "netstat"];
+
+/// Joins `/proc/net` files into one text, each after a `==> /proc/net/<name>
+/// <==` line as `head` prints several files, so that they are recorded as a
+/// single sample.
+pub fn format_proc_net_files(files: &[(&str, String)]) -> String {
+    files
+        .iter()
+        .map(|(name, input)| format!("==> /proc/net/{} <==\n{}", name, input))
+        .collect()
+}
+
+/// Splits what `format_proc_net_files` joined back into (name, contents).
+fn split_proc_net_files(input: &str) -> Vec<(&str, String)> {
+    let mut files: Vec<(&str, String)> = Vec::new();
+    for line in input.lines() {
+        let name = line
+            .strip_prefix("==> /proc/net/")
+            .and_then(|line| line.strip_suffix(" <=="));
+        match (name, files.last_mut()) {
+            (Some(name), _) => files.push((name, String::new())),
+            (None, Some((_, contents))) => {
+                contents.push_str(line);
+                contents.push('\n');
             }
-            ["Reset", "Connections", "=", reset_connections] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.reset_connections = reset_connections.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.reset_connections = reset_connections.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Current", "Connections", "=", current_connections] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.current_connections = current_connections.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.current_connections = current_connections.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Segments", "Received", "=", segments_received] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.segments_received = segments_received.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.segments_received = segments_received.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Segments", "Sent", "=", segments_sent] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.segments_sent = segments_sent.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.segments_sent = segments_sent.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Segments", "Retransmitted", "=", segments_retransmitted] => match current_section {
-                "TCP Statistics for IPv4" => {
-                    stats.tcp_ipv4.segments_retransmitted = segments_retransmitted.parse().unwrap()
-                }
-                "TCP Statistics for IPv6" => {
-                    stats.tcp_ipv6.segments_retransmitted = segments_retransmitted.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Datagrams", "Received", "=", datagrams_received] => match current_section {
-                "UDP Statistics for IPv4" => {
-                    stats.udp_ipv4.datagrams_received = datagrams_received.parse().unwrap()
-                }
-                "UDP Statistics for IPv6" => {
-                    stats.udp_ipv6.datagrams_received = datagrams_received.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["No", "Ports", "=", no_ports] => match current_section {
-                "UDP Statistics for IPv4" => stats.udp_ipv4.no_ports = no_ports.parse().unwrap(),
-                "UDP Statistics for IPv6" => stats.udp_ipv6.no_ports = no_ports.parse().unwrap(),
-                _ => (),
-            },
-            ["Receive", "Errors", "=", receive_errors] => match current_section {
-                "UDP Statistics for IPv4" => {
-                    stats.udp_ipv4.receive_errors = receive_errors.parse().unwrap()
-                }
-                "UDP Statistics for IPv6" => {
-                    stats.udp_ipv6.receive_errors = receive_errors.parse().unwrap()
-                }
-                _ => (),
-            },
-            ["Datagrams", "Sent", "=", datagrams_sent] => match current_section {
-                "UDP Statistics for IPv4" => {
-                    stats.udp_ipv4.datagrams_sent = datagrams_sent.parse().unwrap()
-                }
-                "UDP Statistics for IPv6" => {
-                    stats.udp_ipv6.datagrams_sent = datagrams_sent.parse().unwrap()
-                }
-                _ => (),
-            },
-            _ => (),
+            (None, None) => {}
         }
+    }
+    files
+}
 
-        if line.contains("IPv4 Statistics") {
-            current_section = "IPv4";
-        } else if line.contains("IPv6 Statistics") {
-            current_section = "IPv6";
-        } else if line.contains("ICMPv4 Statistics") {
-            current_section = "ICMPv4";
-        } else if line.contains("ICMPv6 Statistics") {
-            current_section = "ICMPv6";
-        } else if line.contains("TCP Statistics for IPv4") {
-            current_section = "TCP Statistics for IPv4";
-        } else if line.contains("TCP Statistics for IPv6") {
-            current_section = "TCP Statistics for IPv6";
-        } else if line.contains("UDP Statistics for IPv4") {
-            current_section = "UDP Statistics for IPv4";
-        } else if line.contains("UDP Statistics for IPv6") {
-            current_section = "UDP Statistics for IPv6";
+/// The `netstat -s -e` counters and the extended ones from the `/proc/net`
+/// files joined by `format_proc_net_files`. Missing files leave their
+/// sections out.
+pub fn parse_proc_net_stats(input: &str) -> (StatsSnapshot, ExtendedStatistics) {
+    let mut snmp = ExtendedStatistics::default();
+    let mut interfaces = None;
+    let mut extended = ExtendedStatistics::default();
+    for (name, contents) in split_proc_net_files(input) {
+        match name {
+            "snmp" => snmp.0.extend(parse_proc_net_netstat(&contents).0),
+            "snmp6" => snmp.0.extend(parse_proc_net_snmp6(&contents).0),
+            "dev" => interfaces = Some(interfaces::parse_proc_net_dev(&contents)),
+            "netstat" => extended = parse_proc_net_netstat(&contents),
+            _ => {}
         }
     }
 
-    stats
+    let mut snapshot = snmp_snapshot(&snmp);
+    if let Some(interfaces) = interfaces {
+        snapshot.sections.insert(0, interface_section(&interfaces));
+    }
+    (snapshot, extended)
+}
+
+/// Short explanations for the `/proc/net/netstat` counters worth knowing
+/// about, as (section, name, description).
+pub const EXTENDED_COUNTER_DESCRIPTIONS: &[(&str, &str, &str)] = &[
+    (
+        "TcpExt",
+        "SyncookiesSent",
+        "SYN cookies sent because the SYN queue was full",
+    ),
+    (
+        "TcpExt",
+        "SyncookiesRecv",
+        "Valid SYN cookies received back",
+    ),
+    ("TcpExt", "SyncookiesFailed", "Invalid SYN cookies received"),
+    (
+        "TcpExt",
+        "EmbryonicRsts",
+        "Resets received for connections in SYN_RECV",
+    ),
+    (
+        "TcpExt",
+        "PruneCalled",
+        "Receive queues pruned because of memory pressure",
+    ),
+    (
+        "TcpExt",
+        "OfoPruned",
+        "Out-of-order queues dropped because of memory pressure",
+    ),
+    ("TcpExt", "TW", "Connections that finished TIME_WAIT"),
+    ("TcpExt", "DelayedACKs", "Delayed ACKs sent"),
+    (
+        "TcpExt",
+        "ListenOverflows",
+        "SYNs dropped because an accept queue was full",
+    ),
+    (
+        "TcpExt",
+        "ListenDrops",
+        "SYNs dropped on listening sockets, for any reason",
+    ),
+    (
+        "TcpExt",
+        "TCPLostRetransmit",
+        "Retransmitted segments that were lost again",
+    ),
+    (
+        "TcpExt",
+        "TCPFastRetrans",
+        "Fast retransmits, triggered by duplicate ACKs or SACK",
+    ),
+    (
+        "TcpExt",
+        "TCPSlowStartRetrans",
+        "Retransmits in slow start after a loss",
+    ),
+    ("TcpExt", "TCPTimeouts", "Retransmission timer expirations"),
+    ("TcpExt", "TCPSynRetrans", "SYN and SYN/ACK retransmits"),
+    (
+        "TcpExt",
+        "TCPOFOQueue",
+        "Segments queued because they arrived out of order",
+    ),
+    (
+        "TcpExt",
+        "TCPOFODrop",
+        "Out-of-order segments dropped for lack of memory",
+    ),
+    (
+        "TcpExt",
+        "TCPRetransFail",
+        "Retransmits that failed to be sent",
+    ),
+    (
+        "TcpExt",
+        "TCPAbortOnData",
+        "Connections reset because data arrived after close",
+    ),
+    (
+        "TcpExt",
+        "TCPAbortOnClose",
+        "Connections reset because unread data was left on close",
+    ),
+    (
+        "TcpExt",
+        "TCPAbortOnMemory",
+        "Connections reset because of memory pressure",
+    ),
+    (
+        "TcpExt",
+        "TCPAbortOnTimeout",
+        "Connections reset after too many retransmits",
+    ),
+    (
+        "TcpExt",
+        "TCPAbortOnLinger",
+        "Connections reset after the linger timeout",
+    ),
+    (
+        "TcpExt",
+        "TCPBacklogDrop",
+        "Segments dropped because the socket backlog was full",
+    ),
+    (
+        "TcpExt",
+        "TCPReqQFullDrop",
+        "SYNs dropped because the SYN queue was full",
+    ),
+    (
+        "TcpExt",
+        "TCPMemoryPressures",
+        "Times TCP entered memory pressure",
+    ),
+    (
+        "IpExt",
+        "InNoRoutes",
+        "Packets dropped because there was no route",
+    ),
+    (
+        "IpExt",
+        "InTruncatedPkts",
+        "Packets shorter than their IP header claims",
+    ),
+    ("IpExt", "InMcastPkts", "Multicast packets received"),
+    ("IpExt", "OutMcastPkts", "Multicast packets sent"),
+    ("IpExt", "InBcastPkts", "Broadcast packets received"),
+    ("IpExt", "OutBcastPkts", "Broadcast packets sent"),
+    ("IpExt", "InOctets", "Bytes received"),
+    ("IpExt", "OutOctets", "Bytes sent"),
+    ("IpExt", "InCsumErrors", "Packets with a bad IP checksum"),
+];
+
+pub fn extended_counter_description(section: &str, name: &str) -> Option<&'static str> {
+    EXTENDED_COUNTER_DESCRIPTIONS
+        .iter()
+        .find(|(s, n, _)| *s == section && *n == name)
+        .map(|(_, _, description)| *description)
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConnectionType {
     Tcp,
     Udp,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConnectionState {
     Established,
     SynSent,
@@ -1146,121 +1030,1225 @@ pub enum ConnectionState {
     Closing,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Connection {
-    pub connection_type: ConnectionType,
-    pub local_address: String,
-    pub foreign_address: String,
-    pub state: ConnectionState,
-    pub pid: String,
+impl ConnectionState {
+    /// Maps a kernel `TCP_*` state number (as found in `/proc/net/tcp` and
+    /// sock_diag replies) onto a `ConnectionState`.
+    pub fn from_tcp_state(state: u8) -> Option<Self> {
+        match state {
+            1 => Some(ConnectionState::Established),
+            2 => Some(ConnectionState::SynSent),
+            3 => Some(ConnectionState::SynRecv),
+            4 => Some(ConnectionState::FinWait1),
+            5 => Some(ConnectionState::FinWait2),
+            6 => Some(ConnectionState::TimeWait),
+            7 => Some(ConnectionState::Close),
+            8 => Some(ConnectionState::CloseWait),
+            9 => Some(ConnectionState::LastAck),
+            10 => Some(ConnectionState::Listen),
+            11 => Some(ConnectionState::Closing),
+            _ => None,
+        }
+    }
+}
+
+/// Per-socket TCP details as reported by the kernel's `struct tcp_info`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TcpInfo {
+    /// Smoothed round trip time in microseconds.
+    pub rtt_us: u32,
+    /// Round trip time variance in microseconds.
+    pub rtt_var_us: u32,
+    /// Congestion window, in segments.
+    pub snd_cwnd: u32,
+    /// Total number of retransmitted segments over the socket's lifetime.
+    pub retransmits: u32,
+    pub bytes_acked: u64,
+    pub bytes_received: u64,
+    pub uid: u32,
+}
+
+/// A pending TCP timer, as `ss -o` shows it: `timer:(keepalive,119min,0)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SocketTimer {
+    /// `on` (retransmit), `keepalive`, `timewait`, `persist` or `unknown`.
+    pub name: String,
+    pub expires_ms: u64,
+    pub retransmits: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Connection {
+    pub connection_type: ConnectionType,
+    pub local_address: String,
+    pub foreign_address: String,
+    pub state: ConnectionState,
+    pub pid: String,
+    /// Send-Q: bytes not yet acknowledged by the peer (LISTEN: max backlog).
+    pub send_queue: Option<u64>,
+    /// Recv-Q: bytes not yet read by the application (LISTEN: pending accepts).
+    pub recv_queue: Option<u64>,
+    pub tcp_info: Option<TcpInfo>,
+    /// Name of the owning process, when the source reports it next to `pid`.
+    pub process_name: Option<String>,
+    /// Owner of the socket.
+    pub uid: Option<u32>,
+    pub inode: Option<u64>,
+    pub timer: Option<SocketTimer>,
+    /// Network namespace the socket was read from (Linux).
+    pub net_namespace: Option<u64>,
+    /// cgroup and container of the owning process (Linux), see `containers`.
+    pub cgroup: Option<String>,
+    pub container_id: Option<String>,
+    /// Reverse DNS name of the foreign address, once it has been resolved.
+    pub foreign_hostname: Option<String>,
+    /// Service name of the well-known port on either end, e.g. `https`.
+    pub service: Option<String>,
+    pub ephemeral_local_port: bool,
+    pub geo: Option<geoip::GeoInfo>,
+    pub local_class: Option<AddressClass>,
+    pub foreign_class: Option<AddressClass>,
+    /// Whether the address is IPv4-mapped IPv6 (`::ffff:a.b.c.d`), whose
+    /// class is that of the IPv4 address it carries.
+    pub local_mapped: bool,
+    pub foreign_mapped: bool,
+}
+
+impl Connection {
+    pub fn new(
+        connection_type: ConnectionType,
+        local_address: String,
+        foreign_address: String,
+        state: ConnectionState,
+        pid: String,
+    ) -> Self {
+        let local = split_address(&local_address).map(|(ip, _)| ip);
+        let foreign = split_address(&foreign_address).map(|(ip, _)| ip);
+
+        Connection {
+            connection_type,
+            local_address,
+            foreign_address,
+            state,
+            pid,
+            send_queue: None,
+            recv_queue: None,
+            tcp_info: None,
+            process_name: None,
+            uid: None,
+            inode: None,
+            timer: None,
+            net_namespace: None,
+            cgroup: None,
+            container_id: None,
+            foreign_hostname: None,
+            service: None,
+            ephemeral_local_port: false,
+            geo: None,
+            local_class: local.as_ref().map(classify_address),
+            foreign_class: foreign.as_ref().map(classify_address),
+            local_mapped: local.as_ref().is_some_and(is_ipv4_mapped),
+            foreign_mapped: foreign.as_ref().is_some_and(is_ipv4_mapped),
+        }
+    }
+
+    pub fn local_endpoint(&self) -> Option<(IpAddr, Option<u16>)> {
+        split_address(&self.local_address)
+    }
+
+    pub fn foreign_endpoint(&self) -> Option<(IpAddr, Option<u16>)> {
+        split_address(&self.foreign_address)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AddressClass {
+    /// `0.0.0.0`, `::`
+    Unspecified,
+    /// `127.0.0.0/8`, `::1`
+    Loopback,
+    /// RFC 1918: `10.0.0.0/8`, `172.16.0.0/12`, `192.168.0.0/16`
+    Private,
+    /// RFC 6598 carrier-grade NAT: `100.64.0.0/10`
+    Cgnat,
+    /// `169.254.0.0/16`, `fe80::/10`
+    LinkLocal,
+    /// IPv6 unique local addresses: `fc00::/7`
+    UniqueLocal,
+    /// `224.0.0.0/4`, `ff00::/8`
+    Multicast,
+    /// `255.255.255.255`
+    Broadcast,
+    Public,
+}
+
+impl AddressClass {
+    /// Whether traffic to this address leaves the local network.
+    pub fn is_external(&self) -> bool {
+        *self == AddressClass::Public
+    }
+}
+
+/// `::ffff:a.b.c.d`, what dual-stack sockets show IPv4 peers as.
+pub fn is_ipv4_mapped(ip: &IpAddr) -> bool {
+    matches!(ip, IpAddr::V6(ip) if ip.to_ipv4_mapped().is_some())
+}
+
+/// Classifies an IP by the range it belongs to. IPv4-mapped IPv6 addresses
+/// (`::ffff:a.b.c.d`) are classified by the IPv4 address they carry, see
+/// `is_ipv4_mapped` to tell them apart.
+pub fn classify_address(ip: &IpAddr) -> AddressClass {
+    match ip {
+        IpAddr::V4(ip) => classify_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => classify_ipv4(&ip),
+            None => classify_ipv6(ip),
+        },
+    }
+}
+
+fn classify_ipv4(ip: &Ipv4Addr) -> AddressClass {
+    let octets = ip.octets();
+    if ip.is_unspecified() {
+        AddressClass::Unspecified
+    } else if ip.is_loopback() {
+        AddressClass::Loopback
+    } else if ip.is_private() {
+        AddressClass::Private
+    } else if octets[0] == 100 && octets[1] & 0xc0 == 64 {
+        AddressClass::Cgnat
+    } else if ip.is_link_local() {
+        AddressClass::LinkLocal
+    } else if ip.is_multicast() {
+        AddressClass::Multicast
+    } else if ip.is_broadcast() {
+        AddressClass::Broadcast
+    } else {
+        AddressClass::Public
+    }
+}
+
+fn classify_ipv6(ip: &Ipv6Addr) -> AddressClass {
+    let first = ip.segments()[0];
+    if ip.is_unspecified() {
+        AddressClass::Unspecified
+    } else if ip.is_loopback() {
+        AddressClass::Loopback
+    } else if first & 0xffc0 == 0xfe80 {
+        AddressClass::LinkLocal
+    } else if first & 0xfe00 == 0xfc00 {
+        AddressClass::UniqueLocal
+    } else if ip.is_multicast() {
+        AddressClass::Multicast
+    } else {
+        AddressClass::Public
+    }
+}
+
+/// Splits an address as printed by netstat/ss into its IP and port.
+///
+/// Handles `1.2.3.4:80`, `[::1]:80`, `[fe80::1%17]:80`, Linux style `:::80`
+/// and wildcard ports (`0.0.0.0:*`). Returns `None` when there is no IP at
+/// all, e.g. `*:*`.
+pub fn split_address(address: &str) -> Option<(IpAddr, Option<u16>)> {
+    let (host, port) = match address.strip_prefix('[') {
+        Some(rest) => rest.split_once("]:")?,
+        None => address.rsplit_once(':')?,
+    };
+
+    // strip the zone index from link-local addresses
+    let host = host.split_once('%').map_or(host, |(host, _)| host);
+
+    let ip = host.parse().ok()?;
+    Some((ip, port.parse().ok()))
+}
+
+fn parse_connection_state(token: &str) -> Option<ConnectionState> {
+    let state = match token {
+        "ESTABLISHED" => ConnectionState::Established,
+        "SYN_SENT" => ConnectionState::SynSent,
+        "SYN_RECV" => ConnectionState::SynRecv,
+        "FIN_WAIT1" => ConnectionState::FinWait1,
+        "FIN_WAIT2" => ConnectionState::FinWait2,
+        "TIME_WAIT" => ConnectionState::TimeWait,
+        "CLOSE" => ConnectionState::Close,
+        "CLOSE_WAIT" => ConnectionState::CloseWait,
+        "LAST_ACK" => ConnectionState::LastAck,
+        "LISTEN" => ConnectionState::Listen,
+        "CLOSING" => ConnectionState::Closing,
+        // Windows spellings
+        "LISTENING" => ConnectionState::Listen,
+        "SYN_RECEIVED" => ConnectionState::SynRecv,
+        "FIN_WAIT_1" => ConnectionState::FinWait1,
+        "FIN_WAIT_2" => ConnectionState::FinWait2,
+        "CLOSED" => ConnectionState::Close,
+        _ => return None,
+    };
+    Some(state)
+}
+
+/// Parses `netstat -ano` (Windows) or `netstat -tanp` / `netstat -uanp`
+/// (Linux) output. Only the Linux variant carries Recv-Q/Send-Q.
+pub fn parse_connections(input: &str) -> Vec<Connection> {
+    let mut connections = Vec::new();
+
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .skip(2);
+
+    for line in lines {
+        if let Some(connection) = parse_connection_line(line) {
+            connections.push(connection);
+        }
+    }
+
+    connections
+}
+
+/// One row of `netstat` output, Windows or Linux. `None` for anything else,
+/// headers included.
+pub fn parse_connection_line(line: &str) -> Option<Connection> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    if tokens.len() < 4 {
+        return None;
+    }
+
+    match tokens[0] {
+        "TCP" | "UDP" => parse_windows_connection(&tokens),
+        "tcp" | "tcp6" | "udp" | "udp6" => parse_linux_connection(&tokens),
+        _ => None,
+    }
+}
+
+fn parse_windows_connection(tokens: &[&str]) -> Option<Connection> {
+    let connection_type = match tokens[0] {
+        "TCP" => ConnectionType::Tcp,
+        _ => ConnectionType::Udp,
+    };
+
+    let local_address = tokens[1];
+    let foreign_address = tokens[2];
+    // UDP sockets have no state column at all
+    let (state, pid) = match (connection_type, tokens.len()) {
+        (ConnectionType::Udp, 4) => (ConnectionState::Close, tokens[3]),
+        _ => (parse_connection_state(tokens[3])?, *tokens.get(4)?),
+    };
+
+    Some(Connection::new(
+        connection_type,
+        local_address.to_string(),
+        foreign_address.to_string(),
+        state,
+        pid.to_string(),
+    ))
+}
+
+// Proto Recv-Q Send-Q Local Address Foreign Address State PID/Program name
+// UDP sockets usually leave the State column blank, in which case they are
+// reported as `Close`, same as the kernel does for unconnected UDP sockets.
+fn parse_linux_connection(tokens: &[&str]) -> Option<Connection> {
+    if tokens.len() < 5 {
+        return None;
+    }
+
+    let connection_type = if tokens[0].starts_with("tcp") {
+        ConnectionType::Tcp
+    } else {
+        ConnectionType::Udp
+    };
+
+    let recv_queue = tokens[1].parse().ok()?;
+    let send_queue = tokens[2].parse().ok()?;
+    let local_address = tokens[3];
+    let foreign_address = tokens[4];
+
+    let mut rest = tokens[5..].iter();
+    let state = match tokens
+        .get(5)
+        .and_then(|token| parse_connection_state(token))
+    {
+        Some(state) => {
+            rest.next();
+            state
+        }
+        None if connection_type == ConnectionType::Udp => ConnectionState::Close,
+        None => return None,
+    };
+    let pid = rest
+        .next()
+        .and_then(|program| program.split_once('/'))
+        .map(|(pid, _)| pid)
+        .unwrap_or("0");
+
+    let mut connection = Connection::new(
+        connection_type,
+        local_address.to_string(),
+        foreign_address.to_string(),
+        state,
+        pid.to_string(),
+    );
+    connection.recv_queue = Some(recv_queue);
+    connection.send_queue = Some(send_queue);
+    Some(connection)
+}
+
+/// Parses `ss -tanp` output, with or without the `Netid` column that `-u`
+/// adds, and the `-e` (UID, inode) and `-o` (timer) details. PID and process
+/// name are those of the first process in the `users:` column.
+pub fn parse_ss(input: &str) -> Vec<Connection> {
+    input.lines().filter_map(parse_ss_line).collect()
+}
+
+/// One row of `ss` output, `None` for headers and non-TCP/UDP sockets.
+pub fn parse_ss_line(line: &str) -> Option<Connection> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (netid, tokens) = match *tokens.first()? {
+        "tcp" => (Some(ConnectionType::Tcp), &tokens[1..]),
+        "udp" => (Some(ConnectionType::Udp), &tokens[1..]),
+        _ => (None, &tokens[..]),
+    };
+
+    // State Recv-Q Send-Q Local Address:Port Peer Address:Port Process
+    if tokens.len() < 5 {
+        return None;
+    }
+
+    let state = parse_ss_state(tokens[0])?;
+    // without a Netid column only UDP sockets are UNCONN
+    let connection_type = netid.unwrap_or(match tokens[0] {
+        "UNCONN" => ConnectionType::Udp,
+        _ => ConnectionType::Tcp,
+    });
+    let recv_queue = tokens[1].parse().ok()?;
+    let send_queue = tokens[2].parse().ok()?;
+
+    // process names may contain spaces, so `users:((...))` is cut out of the
+    // rest of the line before splitting that into `key:value` fields
+    let details = tokens[5..].join(" ");
+    let (users, details) = match details.find("users:((") {
+        Some(start) => {
+            let end = details[start..]
+                .find("))")
+                .map_or(details.len(), |end| start + end + 2);
+            let users = &details[start + "users:((".len()..end];
+            (
+                Some(users.to_string()),
+                format!("{}{}", &details[..start], &details[end..]),
+            )
+        }
+        None => (None, details),
+    };
+
+    let mut connection = Connection::new(
+        connection_type,
+        ss_address(tokens[3]),
+        ss_address(tokens[4]),
+        state,
+        "0".to_string(),
+    );
+    connection.recv_queue = Some(recv_queue);
+    connection.send_queue = Some(send_queue);
+
+    // ("nginx",pid=123,fd=6),("nginx",pid=124,fd=6))
+    if let Some(users) = users {
+        connection.process_name = users.split('"').nth(1).map(str::to_string);
+        if let Some((_, rest)) = users.split_once("pid=") {
+            let pid: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            if !pid.is_empty() {
+                connection.pid = pid;
+            }
+        }
+    }
+
+    for field in details.split_whitespace() {
+        if let Some(timer) = field.strip_prefix("timer:(") {
+            connection.timer = parse_ss_timer(timer.trim_end_matches(')'));
+        } else if let Some(uid) = field.strip_prefix("uid:") {
+            connection.uid = uid.parse().ok();
+        } else if let Some(inode) = field.strip_prefix("ino:") {
+            // TIME-WAIT sockets have no inode left
+            connection.inode = inode.parse().ok().filter(|inode| *inode != 0);
+        }
+    }
+    // `-e` leaves out `uid:` for root
+    if connection.inode.is_some() && connection.uid.is_none() {
+        connection.uid = Some(0);
+    }
+
+    Some(connection)
+}
+
+/// `keepalive,119min,0`: name, time left and retransmit count.
+fn parse_ss_timer(timer: &str) -> Option<SocketTimer> {
+    let mut parts = timer.split(',');
+    let name = parts.next()?.to_string();
+    let expires_ms = parse_ss_duration(parts.next()?)?;
+    let retransmits = parts.next()?.parse().ok()?;
+    Some(SocketTimer {
+        name,
+        expires_ms,
+        retransmits,
+    })
+}
+
+/// `ss` prints timers as e.g. `2min`, `1min5sec`, `5.200ms` or `200ms`.
+fn parse_ss_duration(duration: &str) -> Option<u64> {
+    let (minutes, rest) = match duration.split_once("min") {
+        Some((minutes, rest)) => (minutes.parse::<u64>().ok()?, rest),
+        None => (0, duration),
+    };
+    let ms = if rest.is_empty() {
+        0
+    } else if let Some(secs) = rest.strip_suffix("sec") {
+        secs.parse::<u64>().ok()? * 1000
+    } else if let Some(ms) = rest.strip_suffix("ms") {
+        match ms.split_once('.') {
+            Some((secs, ms)) => secs.parse::<u64>().ok()? * 1000 + ms.parse::<u64>().ok()?,
+            None => ms.parse().ok()?,
+        }
+    } else {
+        return None;
+    };
+    Some(minutes * 60_000 + ms)
+}
+
+fn parse_ss_state(token: &str) -> Option<ConnectionState> {
+    match token {
+        "ESTAB" => Some(ConnectionState::Established),
+        "UNCONN" => Some(ConnectionState::Close),
+        _ => parse_connection_state(&token.replace('-', "_")),
+    }
+}
+
+/// `ss` puts the interface after the brackets, `[fe80::1]%eth0:546`, where
+/// `split_address` expects it inside.
+fn ss_address(token: &str) -> String {
+    match token.split_once("]%") {
+        Some((host, rest)) => match rest.rsplit_once(':') {
+            Some((zone, port)) => format!("{}%{}]:{}", host, zone, port),
+            None => token.to_string(),
+        },
+        None => token.to_string(),
+    }
+}
+
+/// Parses `lsof -i -n -P` output from Linux, macOS or the BSDs into the
+/// connections and, one per PID, the processes holding them. Sockets without
+/// a peer get `*:*` as foreign address, UDP sockets without a state `Close`.
+pub fn parse_lsof(input: &str) -> (Vec<Connection>, Vec<Process>) {
+    let mut connections = Vec::new();
+    let mut processes: Vec<Process> = Vec::new();
+
+    for (connection, process) in input.lines().filter_map(parse_lsof_line) {
+        connections.push(connection);
+        if !processes.iter().any(|known| known.pid == process.pid) {
+            processes.push(process);
+        }
+    }
+
+    (connections, processes)
+}
+
+/// One row of `lsof -i` output, `None` for the header and anything that is
+/// not a TCP or UDP socket.
+pub fn parse_lsof_line(line: &str) -> Option<(Connection, Process)> {
+    // COMMAND PID USER FD TYPE DEVICE SIZE/OFF NODE NAME
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let pid = tokens.get(1)?;
+    if !pid.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let command = lsof_unescape(tokens[0]);
+    let user = tokens.get(2)?;
+    let ipv6 = *tokens.get(4)? == "IPv6";
+
+    // SIZE/OFF is left blank for some sockets, so look for NODE from the end
+    let node = tokens
+        .iter()
+        .rposition(|token| *token == "TCP" || *token == "UDP")?;
+    let connection_type = match tokens[node] {
+        "TCP" => ConnectionType::Tcp,
+        _ => ConnectionType::Udp,
+    };
+    let name = tokens.get(node + 1)?;
+    let (local_address, foreign_address) = name.split_once("->").unwrap_or((name, "*:*"));
+    let state = match tokens
+        .get(node + 2)
+        .and_then(|token| token.strip_prefix('('))
+        .and_then(|token| token.strip_suffix(')'))
+        .and_then(parse_connection_state)
+    {
+        Some(state) => state,
+        None if connection_type == ConnectionType::Udp => ConnectionState::Close,
+        None => return None,
+    };
+
+    let mut connection = Connection::new(
+        connection_type,
+        lsof_address(local_address, ipv6),
+        foreign_address.to_string(),
+        state,
+        pid.to_string(),
+    );
+    connection.process_name = Some(command.clone());
+    // USER is the login name, or the UID when there is none (or with `-l`)
+    connection.uid = user.parse().ok();
+    // Linux shows the socket inode as DEVICE, macOS a kernel address
+    connection.inode = tokens.get(5).and_then(|device| device.parse().ok());
+
+    let mut process = Process::new(command, pid.to_string());
+    process.user = Some(user.to_string());
+    Some((connection, process))
+}
+
+/// `lsof` shows any-address binds as `*:port` for both families.
+fn lsof_address(address: &str, ipv6: bool) -> String {
+    match address.strip_prefix("*:") {
+        Some(port) if ipv6 => format!("[::]:{}", port),
+        Some(port) => format!("0.0.0.0:{}", port),
+        None => address.to_string(),
+    }
+}
+
+/// `lsof` escapes spaces and other unprintable characters in COMMAND as
+/// `\xNN`.
+fn lsof_unescape(command: &str) -> String {
+    let mut out = String::new();
+    let mut rest = command;
+    while let Some(start) = rest.find("\\x") {
+        out.push_str(&rest[..start]);
+        let hex = rest.get(start + 2..start + 4);
+        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte) => {
+                out.push(byte as char);
+                rest = &rest[start + 4..];
+            }
+            None => {
+                out.push_str("\\x");
+                rest = &rest[start + 2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parses `/proc/net/{tcp,tcp6,udp,udp6}`. The files only carry the socket
+/// inode, so `owners` (inode to PID) is used to fill in `pid`.
+pub fn parse_proc_net(
+    input: &str,
+    connection_type: ConnectionType,
+    owners: &HashMap<u32, String>,
+) -> Vec<Connection> {
+    let mut connections = Vec::new();
+
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .skip(1);
+
+    for line in lines {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+        if tokens.len() < 10 {
+            continue;
+        }
+
+        let (Some(local_address), Some(foreign_address)) = (
+            parse_proc_net_address(tokens[1]),
+            parse_proc_net_address(tokens[2]),
+        ) else {
+            continue;
+        };
+        let Some(state) = u8::from_str_radix(tokens[3], 16)
+            .ok()
+            .and_then(ConnectionState::from_tcp_state)
+        else {
+            continue;
+        };
+        let Some((send_queue, recv_queue)) = tokens[4].split_once(':') else {
+            continue;
+        };
+        let inode: u32 = tokens[9].parse().unwrap_or(0);
+        let pid = owners
+            .get(&inode)
+            .cloned()
+            .unwrap_or_else(|| "0".to_string());
+
+        let mut connection = Connection::new(
+            connection_type,
+            local_address.to_string(),
+            foreign_address.to_string(),
+            state,
+            pid,
+        );
+        connection.send_queue = u64::from_str_radix(send_queue, 16).ok();
+        connection.recv_queue = u64::from_str_radix(recv_queue, 16).ok();
+        connections.push(connection);
+    }
+
+    connections
+}
+
+// `0100007F:0035` -> `127.0.0.1:53`. Each 32-bit word of the address is
+// printed in host byte order.
+fn parse_proc_net_address(token: &str) -> Option<SocketAddr> {
+    let (address, port) = token.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for chunk in address.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+pub fn group_connections_by_pid(connections: Vec<Connection>) -> HashMap<String, Vec<Connection>> {
+    let mut connections_by_pid = HashMap::new();
+
+    for connection in connections {
+        let pid = connection.pid.to_string();
+        let connections = connections_by_pid.entry(pid).or_insert(Vec::new());
+        connections.push(connection);
+    }
+
+    connections_by_pid
+}
+
+pub fn group_connections_by_service(
+    connections: Vec<Connection>,
+) -> HashMap<String, Vec<Connection>> {
+    let mut connections_by_service = HashMap::new();
+
+    for connection in connections {
+        let service = connection
+            .service
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let connections = connections_by_service.entry(service).or_insert(Vec::new());
+        connections.push(connection);
+    }
+
+    connections_by_service
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Process {
+    pub image_name: String,
+    pub pid: String,
+    pub session_name: String,
+    pub session_number: String,
+    /// `None` when the source does not report it (`tasklist` doesn't).
+    pub parent_pid: Option<String>,
+    /// Owning user: `DOMAIN\user` from the process token or `tasklist /v`,
+    /// the user name (or UID, if it has none) on Linux and from `lsof`.
+    pub user: Option<String>,
+    /// Network namespace, cgroup and container (Linux), see `containers`.
+    pub net_namespace: Option<u64>,
+    pub cgroup: Option<String>,
+    pub container_id: Option<String>,
+}
+
+impl Process {
+    /// A process with only its name and PID known, sources fill in the rest.
+    pub fn new(image_name: String, pid: String) -> Self {
+        Process {
+            image_name,
+            pid,
+            session_name: String::new(),
+            session_number: String::new(),
+            parent_pid: None,
+            user: None,
+            net_namespace: None,
+            cgroup: None,
+            container_id: None,
+        }
+    }
+}
+
+// FIXME: This is a mess, windows is a mess :(
+//        Output from `tasklist` is not consistent, sometimes the image name is
+//        cut off, because the process name is too long...
+pub fn parse_processes(input: &str) -> Vec<Process> {
+    let mut processes = Vec::new();
+
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .skip(1);
+
+    for line in lines {
+        if let Some(process) = parse_process_line(line) {
+            processes.push(process);
+        }
+    }
+
+    processes
+}
+
+/// One row of `tasklist /fo csv` output, or `tasklist /v /fo csv`, which
+/// adds the user name. The header row parses too, it has the same shape.
+pub fn parse_process_line(line: &str) -> Option<Process> {
+    // "Image Name","PID","Session Name","Session#","Mem Usage"[,"Status",
+    // "User Name","CPU Time","Window Title"]
+    let tokens = split_csv_line(line);
+
+    if tokens.len() < 5 {
+        return None;
+    }
+
+    let mut tokens = tokens.into_iter();
+    let image_name = tokens.next()?;
+    let pid = tokens.next()?;
+    let session_name = tokens.next()?;
+    let session_number = tokens.next()?;
+    // processes of other users show as "N/A" unless we are elevated
+    let user = tokens
+        .nth(2)
+        .filter(|user| !user.is_empty() && user != "N/A");
+
+    let mut process = Process::new(image_name, pid);
+    process.session_name = session_name;
+    process.session_number = session_number;
+    process.user = user;
+    Some(process)
+}
+
+/// Splits a CSV line into its fields, without the quotes. The `Mem Usage`
+/// column of `tasklist` has a comma in it in most locales.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+/// Parses `/proc/<pid>/stat`. The command name is wrapped in parentheses and
+/// may itself contain spaces and `)`, so fields are counted from the last `)`.
+pub fn parse_proc_stat(input: &str) -> Option<Process> {
+    let (pid, rest) = input.split_once(" (")?;
+    let (comm, fields) = rest.rsplit_once(") ")?;
+
+    // state ppid pgrp session ...
+    let fields: Vec<&str> = fields.split_whitespace().collect();
+    if fields.len() < 4 {
+        return None;
+    }
+
+    let mut process = Process::new(comm.to_string(), pid.trim().to_string());
+    process.session_number = fields[3].to_string();
+    process.parent_pid = Some(fields[1].to_string());
+    Some(process)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Route {
+    pub destination: IpAddr,
+    pub prefix_len: u8,
+    /// `None` for on-link routes.
+    pub gateway: Option<IpAddr>,
+    /// Device name on Linux. `route print` identifies IPv4 interfaces by
+    /// their address and IPv6 interfaces by index.
+    pub interface: String,
+    pub metric: u32,
+}
+
+impl Route {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.destination, ip) {
+            (IpAddr::V4(destination), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len.min(32) as u32)
+                    .unwrap_or(0);
+                u32::from(destination) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(destination), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len.min(128) as u32)
+                    .unwrap_or(0);
+                u128::from(destination) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The route the kernel would pick for `ip`: the longest matching prefix,
+/// the lowest metric among those.
+pub fn route_for<'a>(routes: &'a [Route], ip: &IpAddr) -> Option<&'a Route> {
+    routes
+        .iter()
+        .filter(|route| route.contains(ip))
+        .max_by_key(|route| (route.prefix_len, std::cmp::Reverse(route.metric)))
+}
+
+// `RTF_GATEWAY` and `RTF_REJECT` from `linux/route.h`
+const RTF_GATEWAY: u32 = 0x0002;
+const RTF_REJECT: u32 = 0x0200;
+
+/// Parses `/proc/net/route`. Addresses are printed as 32-bit words in host
+/// byte order, like in `/proc/net/tcp`.
+pub fn parse_proc_net_route(input: &str) -> Vec<Route> {
+    let mut routes = Vec::new();
+
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .skip(1);
+
+    for line in lines {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        // Iface Destination Gateway Flags RefCnt Use Metric Mask MTU Window IRTT
+        if tokens.len() < 8 {
+            continue;
+        }
+
+        let word = |token: &str| {
+            u32::from_str_radix(token, 16)
+                .ok()
+                .map(|word| Ipv4Addr::from(word.to_ne_bytes()))
+        };
+        let (Some(destination), Some(gateway), Some(mask)) =
+            (word(tokens[1]), word(tokens[2]), word(tokens[7]))
+        else {
+            continue;
+        };
+        let flags = u32::from_str_radix(tokens[3], 16).unwrap_or(0);
+        if flags & RTF_REJECT != 0 {
+            continue;
+        }
+
+        routes.push(Route {
+            destination: IpAddr::V4(destination),
+            prefix_len: u32::from(mask).count_ones() as u8,
+            gateway: (flags & RTF_GATEWAY != 0).then_some(IpAddr::V4(gateway)),
+            interface: tokens[0].to_string(),
+            metric: tokens[6].parse().unwrap_or(0),
+        });
+    }
+
+    routes
+}
+
+/// Parses `/proc/net/ipv6_route`. Unlike the other `/proc/net` files the
+/// addresses are plain big-endian hex and there is no header.
+pub fn parse_proc_net_ipv6_route(input: &str) -> Vec<Route> {
+    let mut routes = Vec::new();
+
+    for line in input.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        // dest dest_len src src_len next_hop metric refcnt use flags iface
+        if tokens.len() < 10 {
+            continue;
+        }
+
+        let (Ok(destination), Ok(prefix_len), Ok(next_hop)) = (
+            u128::from_str_radix(tokens[0], 16),
+            u8::from_str_radix(tokens[1], 16),
+            u128::from_str_radix(tokens[4], 16),
+        ) else {
+            continue;
+        };
+        let flags = u32::from_str_radix(tokens[8], 16).unwrap_or(0);
+        if flags & RTF_REJECT != 0 {
+            continue;
+        }
+
+        routes.push(Route {
+            destination: IpAddr::V6(Ipv6Addr::from(destination)),
+            prefix_len,
+            gateway: (next_hop != 0).then(|| IpAddr::V6(Ipv6Addr::from(next_hop))),
+            interface: tokens[9].to_string(),
+            metric: u32::from_str_radix(tokens[5], 16).unwrap_or(0),
+        });
+    }
+
+    routes
+}
+
+/// Parses the active routes from Windows `route print`. Long IPv6 rows wrap
+/// the gateway onto the next line.
+pub fn parse_route_print(input: &str) -> Vec<Route> {
+    let mut routes = Vec::new();
+    let mut ipv6 = false;
+    let mut active = false;
+    // IPv6 row still waiting for its gateway
+    let mut pending: Option<Route> = None;
+
+    for line in input.lines().map(|line| line.trim()) {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let gateway = |token: &str| match token {
+            "On-link" => None,
+            token => token.parse().ok(),
+        };
+
+        if let Some(mut route) = pending.take() {
+            if tokens.len() == 1 {
+                route.gateway = gateway(tokens[0]);
+                routes.push(route);
+                continue;
+            }
+            routes.push(route);
+        }
+
+        if line.starts_with("IPv4 Route Table") {
+            ipv6 = false;
+            continue;
+        }
+        if line.starts_with("IPv6 Route Table") {
+            ipv6 = true;
+            continue;
+        }
+        if line.starts_with("Active Routes:") {
+            active = true;
+            continue;
+        }
+        if line.starts_with("Persistent Routes:") || line.starts_with('=') {
+            active = false;
+            continue;
+        }
+        if !active {
+            continue;
+        }
+
+        if ipv6 {
+            // If Metric NetworkDestination Gateway
+            if tokens.len() < 3 {
+                continue;
+            }
+            let (Ok(metric), Some((destination, prefix_len))) =
+                (tokens[1].parse(), tokens[2].split_once('/'))
+            else {
+                continue;
+            };
+            let (Ok(destination), Ok(prefix_len)) = (destination.parse(), prefix_len.parse())
+            else {
+                continue;
+            };
+
+            let route = Route {
+                destination: IpAddr::V6(destination),
+                prefix_len,
+                gateway: None,
+                interface: tokens[0].to_string(),
+                metric,
+            };
+            match tokens.get(3) {
+                Some(token) => routes.push(Route {
+                    gateway: gateway(token),
+                    ..route
+                }),
+                None => pending = Some(route),
+            }
+        } else {
+            // Network Destination, Netmask, Gateway, Interface, Metric
+            if tokens.len() < 5 {
+                continue;
+            }
+            let (Ok(destination), Ok(mask), Ok(metric)) = (
+                tokens[0].parse::<Ipv4Addr>(),
+                tokens[1].parse::<Ipv4Addr>(),
+                tokens[4].parse(),
+            ) else {
+                continue;
+            };
+
+            routes.push(Route {
+                destination: IpAddr::V4(destination),
+                prefix_len: u32::from(mask).count_ones() as u8,
+                gateway: gateway(tokens[2]),
+                interface: tokens[3].to_string(),
+                metric,
+            });
+        }
+    }
+
+    routes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum NeighborState {
+    /// Learned from traffic, will expire.
+    Dynamic,
+    /// Configured by hand (or multicast/broadcast entries), never expires.
+    Static,
+    /// Resolution pending or failed, there is no hardware address.
+    Incomplete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Neighbor {
+    pub address: IpAddr,
+    /// `aa:bb:cc:dd:ee:ff`.
+    pub mac: Option<String>,
+    pub interface: String,
+    pub state: NeighborState,
 }
 
-pub fn parse_connections(input: &str) -> Vec<Connection> {
-    let mut connections = Vec::new();
+// `aa-bb-cc-dd-ee-ff` or `aa:bb:cc:dd:ee:ff` -> `aa:bb:cc:dd:ee:ff`, all
+// zeros (Windows' placeholder for unresolved entries) -> None
+fn normalize_mac(mac: &str) -> Option<String> {
+    let mac = mac.replace('-', ":").to_ascii_lowercase();
+    if mac.is_empty() || mac.split(':').all(|b| b.trim_start_matches('0').is_empty()) {
+        return None;
+    }
+    Some(mac)
+}
+
+// `ATF_COM` and `ATF_PERM` from `linux/if_arp.h`
+const ATF_COM: u32 = 0x02;
+const ATF_PERM: u32 = 0x04;
+
+/// Parses `/proc/net/arp` (IPv4 only).
+pub fn parse_proc_net_arp(input: &str) -> Vec<Neighbor> {
+    let mut neighbors = Vec::new();
 
     let lines = input
         .lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .skip(2);
+        .skip(1);
 
     for line in lines {
         let tokens: Vec<&str> = line.split_whitespace().collect();
 
-        if tokens.len() < 4 {
+        // IP address, HW type, Flags, HW address, Mask, Device
+        if tokens.len() < 6 {
             continue;
         }
-
-        let connection_type = match tokens[0] {
-            "TCP" => ConnectionType::Tcp,
-            "UDP" => ConnectionType::Udp,
-            _ => continue,
+        let Ok(address) = tokens[0].parse() else {
+            continue;
         };
-
-        let local_address = tokens[1];
-        let foreign_address = tokens[2];
-        let state = match tokens[3] {
-            "ESTABLISHED" => ConnectionState::Established,
-            "SYN_SENT" => ConnectionState::SynSent,
-            "SYN_RECV" => ConnectionState::SynRecv,
-            "FIN_WAIT1" => ConnectionState::FinWait1,
-            "FIN_WAIT2" => ConnectionState::FinWait2,
-            "TIME_WAIT" => ConnectionState::TimeWait,
-            "CLOSE" => ConnectionState::Close,
-            "CLOSE_WAIT" => ConnectionState::CloseWait,
-            "LAST_ACK" => ConnectionState::LastAck,
-            "LISTEN" => ConnectionState::Listen,
-            "CLOSING" => ConnectionState::Closing,
-            _ => continue,
+        let flags = u32::from_str_radix(tokens[2].trim_start_matches("0x"), 16).unwrap_or(0);
+
+        let state = if flags & ATF_PERM != 0 {
+            NeighborState::Static
+        } else if flags & ATF_COM != 0 {
+            NeighborState::Dynamic
+        } else {
+            NeighborState::Incomplete
         };
-        let pid = tokens[4];
 
-        connections.push(Connection {
-            connection_type,
-            local_address: local_address.to_string(),
-            foreign_address: foreign_address.to_string(),
+        neighbors.push(Neighbor {
+            address,
+            mac: normalize_mac(tokens[3]).filter(|_| state != NeighborState::Incomplete),
+            interface: tokens[5].to_string(),
             state,
-            pid: pid.to_string(),
         });
     }
 
-    connections
+    neighbors
 }
 
-pub fn group_connections_by_pid(connections: Vec<Connection>) -> HashMap<String, Vec<Connection>> {
-    let mut connections_by_pid = HashMap::new();
-
-    for connection in connections {
-        let pid = connection.pid.to_string();
-        let connections = connections_by_pid.entry(pid).or_insert(Vec::new());
-        connections.push(connection);
-    }
+/// Parses `ip neigh show`, used for the IPv6 neighbors that `/proc/net/arp`
+/// does not have, e.g.
+/// `fe80::1 dev eth0 lladdr a0:b5:49:12:34:56 router REACHABLE`.
+pub fn parse_ip_neigh(input: &str) -> Vec<Neighbor> {
+    let mut neighbors = Vec::new();
 
-    connections_by_pid
-}
+    for line in input.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some(Ok(address)) = tokens.first().map(|token| token.parse()) else {
+            continue;
+        };
 
-#[derive(Debug, Clone, Serialize)]
-pub struct Process {
-    pub image_name: String,
-    pub pid: String,
-    pub session_name: String,
-    pub session_number: String,
-}
+        let value = |key: &str| {
+            tokens
+                .iter()
+                .position(|token| *token == key)
+                .and_then(|i| tokens.get(i + 1))
+        };
+        let state = match tokens.last().copied() {
+            Some("PERMANENT" | "NOARP") => NeighborState::Static,
+            Some("INCOMPLETE" | "FAILED") => NeighborState::Incomplete,
+            _ => NeighborState::Dynamic,
+        };
 
-// FIXME: This is a mess, windows is a mess :(
-//        Output from `tasklist` is not consistent, sometimes the image name is
-//        cut off, because the process name is too long...
-pub fn parse_processes(input: &str) -> Vec<Process> {
-    let mut processes = Vec::new();
+        neighbors.push(Neighbor {
+            address,
+            mac: value("lladdr").and_then(|mac| normalize_mac(mac)),
+            interface: value("dev").map_or_else(String::new, |dev| dev.to_string()),
+            state,
+        });
+    }
 
-    let lines = input
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .skip(1);
+    neighbors
+}
 
-    for line in lines {
-        let tokens: Vec<&str> = line.split(",").collect();
+/// Parses Windows `arp -a` and `netsh interface ipv6 show neighbors`. Both
+/// group entries under an interface header, `Interface: 192.168.1.20 ---
+/// 0xb` and `Interface 14: Wi-Fi` respectively.
+pub fn parse_arp(input: &str) -> Vec<Neighbor> {
+    let mut neighbors = Vec::new();
+    let mut interface = String::new();
+
+    for line in input.lines().map(|line| line.trim()) {
+        if let Some(header) = line.strip_prefix("Interface") {
+            interface = match header.split_once(": ") {
+                // arp -a
+                Some(("", name)) => name.split(" --- ").next().unwrap_or(name),
+                // netsh
+                Some((_, name)) => name,
+                None => header,
+            }
+            .trim()
+            .to_string();
+            continue;
+        }
 
-        if tokens.len() < 5 {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() < 3 {
             continue;
         }
+        let Ok(address) = tokens[0].parse() else {
+            continue;
+        };
 
-        // prettify field values from `\"value\"` to `value`
-        let image_name = tokens[0].trim_matches('"').to_string();
-        let pid = tokens[1].trim_matches('"').to_string();
-        let session_name = tokens[2].trim_matches('"').to_string();
-        let session_number = tokens[3].trim_matches('"').to_string();
+        let state = match tokens[2].to_ascii_lowercase().as_str() {
+            "static" | "permanent" => NeighborState::Static,
+            "invalid" | "incomplete" | "unreachable" => NeighborState::Incomplete,
+            _ => NeighborState::Dynamic,
+        };
 
-        processes.push(Process {
-            image_name,
-            pid,
-            session_name,
-            session_number,
+        neighbors.push(Neighbor {
+            address,
+            mac: normalize_mac(tokens[1]),
+            interface: interface.clone(),
+            state,
         });
     }
 
-    processes
+    neighbors
 }
 
 #[cfg(test)]
@@ -1539,6 +2527,224 @@ mod tests {
         assert_eq!(stats.udp_ipv6.datagrams_sent, 147179);
     }
 
+    #[test]
+    fn test_parse_stats_per_protocol() {
+        // `netstat -s -p tcp` followed by `netstat -s -p udpv6`
+        let input = r#"
+TCP Statistics for IPv4
+
+  Active Opens                        = 64644
+  Segments Retransmitted              = 24199
+
+Active Connections
+
+  Proto  Local Address          Foreign Address        State
+  TCP    127.0.0.1:5354         127.0.0.1:49669        ESTABLISHED
+
+UDP Statistics for IPv6
+
+  Datagrams Received    = 234072
+  No Ports              = 5641
+"#;
+        let stats = parse_stats(input);
+        assert_eq!(stats.tcp_ipv4.active_opens, 64644);
+        assert_eq!(stats.tcp_ipv4.segments_retransmitted, 24199);
+        assert_eq!(stats.udp_ipv6.datagrams_received, 234072);
+        assert_eq!(stats.udp_ipv6.no_ports, 5641);
+        assert_eq!(stats.udp_ipv4.datagrams_received, 0);
+
+        let snapshot = parse_stats_snapshot(input);
+        let sections: Vec<&str> = snapshot
+            .sections
+            .iter()
+            .map(|section| section.name.as_str())
+            .collect();
+        assert_eq!(sections, ["tcp_ipv4", "udp_ipv6"]);
+        assert_eq!(snapshot.section("tcp_ipv4").unwrap().entries.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_stats_icmp_types() {
+        let input = r#"
+ICMPv4 Statistics
+
+                            Received    Sent
+  Messages                  16443       22923
+  Destination Unreachable   16442       22923
+  Type 3                    16442       22923
+  Type 11                   1           0
+
+ICMPv6 Statistics
+
+  Messages                  19333       36465
+  InType135 = 8344
+  OutType135 = 23234
+  OutType133 = 212
+"#;
+        let snapshot = parse_stats_snapshot(input);
+        assert_eq!(
+            parse_stats(input).icmpv4.messages,
+            NetworkData {
+                received: 16443,
+                sent: 22923
+            }
+        );
+        assert_eq!(
+            snapshot.get("icmpv4", "type_3"),
+            Some(StatValue::Pair(NetworkData {
+                received: 16442,
+                sent: 22923
+            }))
+        );
+        assert_eq!(
+            snapshot.get("icmpv6", "type_135"),
+            Some(StatValue::Pair(NetworkData {
+                received: 8344,
+                sent: 23234
+            }))
+        );
+        assert_eq!(
+            snapshot.get("icmpv6", "type_133"),
+            Some(StatValue::Pair(NetworkData {
+                received: 0,
+                sent: 212
+            }))
+        );
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["icmpv4"]["type_11"]["received"], 1);
+        assert_eq!(json["icmpv6"]["messages"]["sent"], 36465);
+    }
+
+    #[test]
+    fn test_parse_stats_unknown_sections() {
+        let input = r#"
+IPv4 Statistics
+
+  Packets Received                   = 60090845
+
+SCTP Statistics for IPv4
+
+  Packets Received                   = 120
+  Aborted Associations               = 2
+
+Pseudo Statistics
+
+                            Received    Sent
+  Chunks                    10          12
+"#;
+        assert_eq!(parse_stats(input).ipv4.packets_received, 60090845);
+
+        let snapshot = parse_stats_snapshot(input);
+        assert_eq!(
+            snapshot.get("sctp_ipv4", "packets_received"),
+            Some(StatValue::Count(120))
+        );
+        assert_eq!(
+            snapshot.get("sctp_ipv4", "aborted_associations"),
+            Some(StatValue::Count(2))
+        );
+        assert_eq!(snapshot.section("pseudo").unwrap().entries.len(), 1);
+
+        // serialized in the order netstat lists them
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(
+            json,
+            r#"{"ipv4":{"packets_received":60090845},"sctp_ipv4":{"packets_received":120,"aborted_associations":2},"pseudo":{"chunks":{"received":10,"sent":12}}}"#
+        );
+    }
+
+    #[test]
+    fn test_stat_fields() {
+        // the snapshot key of every field is its name, so the typed and the
+        // generic statistics serialize to the same shape
+        for (title, fields) in STATS_SECTIONS {
+            for field in *fields {
+                assert_eq!(
+                    stats_key(field.label.split_whitespace()),
+                    field.name,
+                    "{}",
+                    title
+                );
+                assert!(field.snmp.len() <= 2);
+                assert_eq!(field.snmp.len() == 2, field.pair && !field.snmp.is_empty());
+                assert!(!field.description.starts_with(' '));
+            }
+        }
+
+        let fields = stat_fields();
+        assert_eq!(fields.len(), 9);
+        let bytes = fields["interface"]["bytes"];
+        assert_eq!(bytes.unit, "bytes");
+        assert!(bytes.pair);
+        assert_eq!(
+            fields["tcp_ipv4"]["segments_retransmitted"].snmp,
+            ["Tcp.RetransSegs"]
+        );
+    }
+
+    #[test]
+    fn test_typescript_definitions() {
+        let generated = typescript_definitions();
+        if std::env::var_os("UPDATE_TYPESCRIPT").is_some() {
+            std::fs::write(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/../src/net-stat.ts"),
+                &generated,
+            )
+            .unwrap();
+            return;
+        }
+        // rerun with UPDATE_TYPESCRIPT=1 after changing a `stats_struct!`
+        assert_eq!(include_str!("../../src/net-stat.ts"), generated);
+    }
+
+    #[test]
+    fn test_snmp_snapshot() {
+        let snmp = "Ip: Forwarding DefaultTTL InReceives InHdrErrors
+Ip: 1 64 60090845 3
+Icmp: InMsgs InErrors OutMsgs OutErrors InDestUnreachs OutDestUnreachs
+Icmp: 16443 0 22923 0 16442 22923
+Tcp: RtoAlgorithm ActiveOpens RetransSegs
+Tcp: 1 64644 24199
+";
+        let snmp6 = "Ip6InReceives                   	154566
+Ip6InHdrErrors                  	0
+Icmp6InMsgs                     	19333
+Icmp6OutMsgs                    	36465
+Udp6InDatagrams                 	234072
+UdpLite6InDatagrams             	0
+";
+        let mut stats = parse_proc_net_netstat(snmp);
+        let stats6 = parse_proc_net_snmp6(snmp6);
+        assert_eq!(stats6.get("Ip6", "InReceives"), Some(154566));
+        assert_eq!(stats6.get("UdpLite6", "InDatagrams"), Some(0));
+        stats.0.extend(stats6.0);
+
+        let snapshot = snmp_snapshot(&stats);
+        assert_eq!(
+            snapshot.get("ipv4", "packets_received"),
+            Some(StatValue::Count(60090845))
+        );
+        assert_eq!(
+            snapshot.get("icmpv4", "destination_unreachable"),
+            Some(StatValue::Pair(NetworkData {
+                received: 16442,
+                sent: 22923
+            }))
+        );
+        // not in the input
+        assert_eq!(snapshot.get("ipv4", "datagrams_forwarded"), None);
+        assert!(snapshot.section("interface").is_none());
+        assert!(snapshot.section("tcp_ipv6").is_none());
+
+        let stats = NetworkStatistics::from(&snapshot);
+        assert_eq!(stats.ipv4.received_header_errors, 3);
+        assert_eq!(stats.ipv6.packets_received, 154566);
+        assert_eq!(stats.icmpv6.messages.sent, 36465);
+        assert_eq!(stats.tcp_ipv4.segments_retransmitted, 24199);
+        assert_eq!(stats.udp_ipv6.datagrams_received, 234072);
+    }
+
     #[test]
     fn test_parse_connections() {
         let input = r#"
@@ -1695,6 +2901,405 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_connections_linux() {
+        let input = r#"
+        Active Internet connections (servers and established)
+        Proto Recv-Q Send-Q Local Address           Foreign Address         State       PID/Program name
+        tcp        0      0 127.0.0.53:53           0.0.0.0:*               LISTEN      612/systemd-resolve
+        tcp        0   2896 192.168.1.20:22         192.168.1.5:50514       ESTABLISHED 1834/sshd: user
+        tcp      517      0 192.168.1.20:45678      140.82.121.4:443        CLOSE_WAIT  2291/firefox
+        tcp6       0      0 :::80                   :::*                    LISTEN      -
+        udp        0      0 0.0.0.0:68              0.0.0.0:*                           598/dhclient
+        udp6       0      0 fe80::1:546             :::*                                -"#;
+
+        let connections = parse_connections(input);
+
+        assert_eq!(connections.len(), 6);
+
+        assert_eq!(connections[0].state, ConnectionState::Listen);
+        assert_eq!(connections[0].pid, "612");
+        assert_eq!(connections[0].recv_queue, Some(0));
+
+        assert_eq!(connections[1].local_address, "192.168.1.20:22");
+        assert_eq!(connections[1].foreign_address, "192.168.1.5:50514");
+        assert_eq!(connections[1].send_queue, Some(2896));
+        assert_eq!(connections[1].pid, "1834");
+
+        assert_eq!(connections[2].state, ConnectionState::CloseWait);
+        assert_eq!(connections[2].recv_queue, Some(517));
+
+        assert_eq!(connections[3].pid, "0");
+
+        assert_eq!(connections[4].connection_type, ConnectionType::Udp);
+        assert_eq!(connections[4].state, ConnectionState::Close);
+        assert_eq!(connections[4].pid, "598");
+
+        assert_eq!(connections[5].pid, "0");
+    }
+
+    #[test]
+    fn test_parse_ss() {
+        let input = r#"
+        State  Recv-Q Send-Q  Local Address:Port   Peer Address:Port Process
+        LISTEN 0      4096    127.0.0.53%lo:53          0.0.0.0:*     users:(("systemd-resolve",pid=612,fd=14))
+        ESTAB  0      36       192.168.1.20:22      192.168.1.5:50514 users:(("sshd",pid=1834,fd=4),("sshd",pid=1790,fd=4))
+        TIME-WAIT 0   0        192.168.1.20:45678  140.82.121.4:443
+        LISTEN 0      511              [::]:80             [::]:*"#;
+
+        let connections = parse_ss(input);
+
+        assert_eq!(connections.len(), 4);
+        assert_eq!(connections[0].state, ConnectionState::Listen);
+        assert_eq!(connections[0].pid, "612");
+        assert_eq!(connections[0].recv_queue, Some(0));
+        assert_eq!(connections[0].send_queue, Some(4096));
+        assert!(connections[0].local_endpoint().is_some());
+        assert_eq!(connections[1].state, ConnectionState::Established);
+        assert_eq!(connections[1].pid, "1834");
+        assert_eq!(connections[2].state, ConnectionState::TimeWait);
+        assert_eq!(connections[2].pid, "0");
+        assert_eq!(connections[3].local_address, "[::]:80");
+
+        let udp = parse_ss(
+            "udp UNCONN 0 0 [fe80::1]%eth0:546 [::]:* users:((\"dhclient\",pid=598,fd=6))",
+        );
+        assert_eq!(udp[0].connection_type, ConnectionType::Udp);
+        assert_eq!(udp[0].state, ConnectionState::Close);
+        assert_eq!(udp[0].local_address, "[fe80::1%eth0]:546");
+        assert!(udp[0].local_endpoint().is_some());
+    }
+
+    #[test]
+    fn test_parse_ss_details() {
+        let input = r#"
+        Netid State  Recv-Q Send-Q Local Address:Port  Peer Address:Port Process
+        udp   UNCONN 0      0      127.0.0.53%lo:53        0.0.0.0:*     users:(("systemd-resolve",pid=612,fd=13)) uid:101 ino:20945 sk:1 cgroup:/system.slice/systemd-resolved.service <->
+        tcp   LISTEN 0      511          0.0.0.0:80        0.0.0.0:*     users:(("nginx",pid=1201,fd=6),("nginx",pid=1200,fd=6)) ino:31337 sk:2 cgroup:/system.slice/nginx.service <->
+        tcp   ESTAB  0      0       192.168.1.20:22    192.168.1.5:50514 users:(("sshd",pid=1834,fd=4)) timer:(keepalive,119min,0) ino:51234 sk:5 cgroup:/system.slice/ssh.service <->
+        tcp   ESTAB  0      1448    192.168.1.20:45678 140.82.121.4:443  users:(("Web Content",pid=2291,fd=87)) timer:(on,5.200ms,2) uid:1000 ino:61001 sk:6 cgroup:/user.slice <->
+        tcp   TIME-WAIT 0   0       192.168.1.20:45690 140.82.121.4:443  timer:(timewait,59sec,0) ino:0 sk:7
+        u_str ESTAB  0      0                  * 31444            * 31445"#;
+
+        let connections = parse_ss(input);
+
+        assert_eq!(connections.len(), 5);
+
+        assert_eq!(connections[0].connection_type, ConnectionType::Udp);
+        assert_eq!(
+            connections[0].process_name.as_deref(),
+            Some("systemd-resolve")
+        );
+        assert_eq!(connections[0].pid, "612");
+        assert_eq!(connections[0].uid, Some(101));
+        assert_eq!(connections[0].inode, Some(20945));
+        assert_eq!(connections[0].timer, None);
+
+        // root, so no uid: field
+        assert_eq!(connections[1].pid, "1201");
+        assert_eq!(connections[1].uid, Some(0));
+
+        assert_eq!(
+            connections[2].timer,
+            Some(SocketTimer {
+                name: "keepalive".to_string(),
+                expires_ms: 119 * 60_000,
+                retransmits: 0,
+            })
+        );
+
+        assert_eq!(connections[3].process_name.as_deref(), Some("Web Content"));
+        assert_eq!(connections[3].pid, "2291");
+        assert_eq!(connections[3].uid, Some(1000));
+        assert_eq!(connections[3].send_queue, Some(1448));
+        assert_eq!(connections[3].timer.as_ref().unwrap().expires_ms, 5200);
+        assert_eq!(connections[3].timer.as_ref().unwrap().retransmits, 2);
+
+        assert_eq!(connections[4].state, ConnectionState::TimeWait);
+        assert_eq!(connections[4].pid, "0");
+        assert_eq!(connections[4].process_name, None);
+        assert_eq!(connections[4].inode, None);
+        assert_eq!(connections[4].uid, None);
+        assert_eq!(connections[4].timer.as_ref().unwrap().expires_ms, 59_000);
+    }
+
+    #[test]
+    fn test_parse_ss_duration() {
+        assert_eq!(parse_ss_duration("200ms"), Some(200));
+        assert_eq!(parse_ss_duration("5.200ms"), Some(5200));
+        assert_eq!(parse_ss_duration("59sec"), Some(59_000));
+        assert_eq!(parse_ss_duration("1min5sec"), Some(65_000));
+        assert_eq!(parse_ss_duration("12min"), Some(720_000));
+        assert_eq!(parse_ss_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_parse_lsof_linux() {
+        let input = r#"
+        COMMAND    PID   USER   FD   TYPE DEVICE SIZE/OFF NODE NAME
+        sshd       812   root    3u  IPv4  18432      0t0  TCP *:22 (LISTEN)
+        sshd       812   root    4u  IPv6  18434      0t0  TCP *:22 (LISTEN)
+        firefox   2291  alice   87u  IPv4  51234      0t0  TCP 192.168.1.20:45678->140.82.121.4:443 (ESTABLISHED)
+        firefox   2291  alice   91u  IPv6  51240      0t0  TCP [2001:db8::20]:51002->[2a00:1450:4001::200e]:443 (CLOSE_WAIT)
+        dhclient   598   root    6u  IPv4  17001      0t0  UDP *:68
+        Web\x20Co 3310  alice   40u  IPv4  61002      0t0  UDP 192.168.1.20:40123->1.1.1.1:53
+        node      4100   1001   21u  IPv4  70001      0t0  TCP 127.0.0.1:3000 (LISTEN)"#;
+
+        let (connections, processes) = parse_lsof(input);
+
+        assert_eq!(connections.len(), 7);
+        assert_eq!(connections[0].state, ConnectionState::Listen);
+        assert_eq!(connections[0].local_address, "0.0.0.0:22");
+        assert_eq!(connections[0].foreign_address, "*:*");
+        assert_eq!(connections[0].inode, Some(18432));
+        assert_eq!(connections[0].process_name.as_deref(), Some("sshd"));
+        assert_eq!(connections[0].uid, None);
+        assert_eq!(connections[1].local_address, "[::]:22");
+        assert!(connections[1].local_endpoint().is_some());
+
+        assert_eq!(connections[2].pid, "2291");
+        assert_eq!(connections[2].foreign_address, "140.82.121.4:443");
+        assert_eq!(connections[2].foreign_class, Some(AddressClass::Public));
+        assert_eq!(connections[3].state, ConnectionState::CloseWait);
+        assert_eq!(connections[3].local_address, "[2001:db8::20]:51002");
+
+        assert_eq!(connections[4].connection_type, ConnectionType::Udp);
+        assert_eq!(connections[4].state, ConnectionState::Close);
+        assert_eq!(connections[5].process_name.as_deref(), Some("Web Co"));
+        assert_eq!(connections[5].foreign_address, "1.1.1.1:53");
+        assert_eq!(connections[6].uid, Some(1001));
+
+        let pids: Vec<&str> = processes
+            .iter()
+            .map(|process| process.pid.as_str())
+            .collect();
+        assert_eq!(pids, ["812", "2291", "598", "3310", "4100"]);
+        assert_eq!(processes[1].image_name, "firefox");
+        assert_eq!(processes[1].user.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_parse_lsof_macos() {
+        let input = r#"
+        COMMAND     PID   USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+        launchd       1   root   41u  IPv6 0x8c2d1e2f3a4b5c6d      0t0  TCP *:22 (LISTEN)
+        mDNSRespo   301 _mdnsresponder 8u IPv4 0x8c2d1e2f3a4b5c70 0t0  UDP *:5353
+        rapportd    512  alice    4u  IPv4 0x8c2d1e2f3a4b5c71      0t0  TCP 192.168.1.7:49152->192.168.1.9:50612 (FIN_WAIT_1)
+        Google      987  alice   23u  IPv4 0x8c2d1e2f3a4b5c72      0t0  TCP 192.168.1.7:51000->142.250.74.46:443 (SYN_SENT)
+        Google      987  alice   24u  IPv4 0x8c2d1e2f3a4b5c73      0t0  TCP 192.168.1.7:51001->142.250.74.46:443 (CLOSED)"#;
+
+        let (connections, processes) = parse_lsof(input);
+
+        assert_eq!(connections.len(), 5);
+        assert_eq!(connections[0].local_address, "[::]:22");
+        assert_eq!(connections[0].inode, None);
+        assert_eq!(connections[1].process_name.as_deref(), Some("mDNSRespo"));
+        assert_eq!(connections[1].state, ConnectionState::Close);
+        assert_eq!(connections[2].state, ConnectionState::FinWait1);
+        assert_eq!(connections[3].state, ConnectionState::SynSent);
+        assert_eq!(connections[4].state, ConnectionState::Close);
+        assert_eq!(processes.len(), 4);
+        assert_eq!(processes[1].user.as_deref(), Some("_mdnsresponder"));
+    }
+
+    #[test]
+    fn test_parse_lsof_freebsd() {
+        // no SIZE/OFF column for sockets
+        let input = r#"
+        COMMAND  PID USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+        sshd     744 root    4u  IPv4 0xfffff80003a1b000      TCP *:22 (LISTEN)
+        ntpd     690 ntpd   21u  IPv4 0xfffff80003c2d000      UDP 10.0.0.5:123
+        unbound  701 unbound 5u  unix 0xfffff80003d11000      0t0 /var/run/local_unbound.ctl"#;
+
+        let (connections, processes) = parse_lsof(input);
+
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].local_address, "0.0.0.0:22");
+        assert_eq!(connections[0].state, ConnectionState::Listen);
+        assert_eq!(connections[1].local_address, "10.0.0.5:123");
+        assert_eq!(processes.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_connections_windows_has_no_queues() {
+        let input = r#"
+        Active Connections
+
+        Proto  Local Address          Foreign Address        State           PID
+        TCP    127.0.0.1:57784        127.0.0.1:65001        ESTABLISHED     5124"#;
+
+        let connections = parse_connections(input);
+
+        assert_eq!(connections.len(), 1);
+        assert_eq!(connections[0].send_queue, None);
+        assert_eq!(connections[0].recv_queue, None);
+    }
+
+    #[test]
+    fn test_parse_proc_net() {
+        let input = r#"
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 3500007F:0035 00000000:0000 0A 00000000:00000000 00:00000000 00000000   101        0 20575 1 0000000000000000 100 0 0 10 0
+   1: 1401A8C0:0016 0501A8C0:C5D2 01 00000B50:00000000 01:00000015 00000000     0        0 41234 4 0000000000000000 20 4 29 10 -1
+   2: 1401A8C0:B26E 0479528C:01BB 08 00000000:00000205 00:00000000 00000000  1000        0 0 1 0000000000000000 20 4 29 10 -1"#;
+
+        let owners = HashMap::from([(41234, "1834".to_string())]);
+        let connections = parse_proc_net(input, ConnectionType::Tcp, &owners);
+
+        assert_eq!(connections.len(), 3);
+
+        assert_eq!(connections[0].local_address, "127.0.0.53:53");
+        assert_eq!(connections[0].foreign_address, "0.0.0.0:0");
+        assert_eq!(connections[0].state, ConnectionState::Listen);
+        assert_eq!(connections[0].pid, "0");
+
+        assert_eq!(connections[1].local_address, "192.168.1.20:22");
+        assert_eq!(connections[1].foreign_address, "192.168.1.5:50642");
+        assert_eq!(connections[1].state, ConnectionState::Established);
+        assert_eq!(connections[1].send_queue, Some(2896));
+        assert_eq!(connections[1].recv_queue, Some(0));
+        assert_eq!(connections[1].pid, "1834");
+
+        assert_eq!(connections[2].foreign_address, "140.82.121.4:443");
+        assert_eq!(connections[2].state, ConnectionState::CloseWait);
+        assert_eq!(connections[2].recv_queue, Some(517));
+    }
+
+    #[test]
+    fn test_parse_proc_net_ipv6() {
+        let input = r#"
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:0050 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 00000000000000000000000001000000:0277 00000000000000000000000001000000:D2F0 01 00000000:00000000 00:00000000 00000000  1000        0 34567 1 0000000000000000 20 4 30 10 -1"#;
+
+        let connections = parse_proc_net(input, ConnectionType::Tcp, &HashMap::new());
+
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].local_address, "[::]:80");
+        assert_eq!(connections[1].local_address, "[::1]:631");
+        assert_eq!(connections[1].foreign_address, "[::1]:54000");
+    }
+
+    #[test]
+    fn test_split_address() {
+        let v4: IpAddr = "192.168.100.14".parse().unwrap();
+        let v6_loopback: IpAddr = "::1".parse().unwrap();
+        let link_local: IpAddr = "fe80::2eab:cf7c:88:b019".parse().unwrap();
+
+        assert_eq!(
+            split_address("192.168.100.14:57793"),
+            Some((v4, Some(57793)))
+        );
+        assert_eq!(split_address("[::1]:1420"), Some((v6_loopback, Some(1420))));
+        assert_eq!(
+            split_address("[fe80::2eab:cf7c:88:b019%17]:1900"),
+            Some((link_local, Some(1900)))
+        );
+        assert_eq!(
+            split_address(":::22"),
+            Some(("::".parse().unwrap(), Some(22)))
+        );
+        assert_eq!(
+            split_address("0.0.0.0:*"),
+            Some(("0.0.0.0".parse().unwrap(), None))
+        );
+        assert_eq!(split_address("*:*"), None);
+        assert_eq!(split_address("garbage"), None);
+    }
+
+    #[test]
+    fn test_classify_address() {
+        let class = |ip: &str| classify_address(&ip.parse().unwrap());
+
+        assert_eq!(class("0.0.0.0"), AddressClass::Unspecified);
+        assert_eq!(class("::"), AddressClass::Unspecified);
+        assert_eq!(class("127.0.0.1"), AddressClass::Loopback);
+        assert_eq!(class("127.45.0.9"), AddressClass::Loopback);
+        assert_eq!(class("::1"), AddressClass::Loopback);
+        assert_eq!(class("10.20.30.40"), AddressClass::Private);
+        assert_eq!(class("172.29.112.1"), AddressClass::Private);
+        assert_eq!(class("192.168.100.14"), AddressClass::Private);
+        assert_eq!(class("172.32.0.1"), AddressClass::Public);
+        assert_eq!(class("100.64.0.1"), AddressClass::Cgnat);
+        assert_eq!(class("100.127.255.254"), AddressClass::Cgnat);
+        assert_eq!(class("100.128.0.1"), AddressClass::Public);
+        assert_eq!(class("169.254.12.7"), AddressClass::LinkLocal);
+        assert_eq!(class("fe80::2eab:cf7c:88:b019"), AddressClass::LinkLocal);
+        assert_eq!(class("fd12:3456:789a::1"), AddressClass::UniqueLocal);
+        assert_eq!(class("fc00::1"), AddressClass::UniqueLocal);
+        assert_eq!(class("224.0.0.251"), AddressClass::Multicast);
+        assert_eq!(class("239.255.255.250"), AddressClass::Multicast);
+        assert_eq!(class("ff02::fb"), AddressClass::Multicast);
+        assert_eq!(class("255.255.255.255"), AddressClass::Broadcast);
+        assert_eq!(class("140.82.121.4"), AddressClass::Public);
+        assert_eq!(class("2a00:1450:4001:82b::200e"), AddressClass::Public);
+
+        // IPv4-mapped IPv6 is classified by the embedded IPv4 address
+        assert_eq!(class("::ffff:127.0.0.1"), AddressClass::Loopback);
+        assert_eq!(class("::ffff:192.168.1.1"), AddressClass::Private);
+        assert_eq!(class("::ffff:100.100.1.1"), AddressClass::Cgnat);
+        assert_eq!(class("::ffff:140.82.121.4"), AddressClass::Public);
+        let mapped = |ip: &str| is_ipv4_mapped(&ip.parse().unwrap());
+        assert!(mapped("::ffff:127.0.0.1"));
+        assert!(mapped("::ffff:140.82.121.4"));
+        assert!(!mapped("140.82.121.4"));
+        assert!(!mapped("::1"));
+        assert!(!mapped("2a00:1450:4001:82b::200e"));
+    }
+
+    #[test]
+    fn test_connection_address_classes() {
+        let connection = Connection::new(
+            ConnectionType::Tcp,
+            "192.168.100.14:57793".to_string(),
+            "40.115.3.253:443".to_string(),
+            ConnectionState::Established,
+            "5236".to_string(),
+        );
+        assert_eq!(connection.local_class, Some(AddressClass::Private));
+        assert_eq!(connection.foreign_class, Some(AddressClass::Public));
+        assert!(!connection.local_mapped && !connection.foreign_mapped);
+
+        let dual_stack = Connection::new(
+            ConnectionType::Tcp,
+            "[::ffff:192.168.100.14]:8080".to_string(),
+            "[::ffff:40.115.3.253]:50123".to_string(),
+            ConnectionState::Established,
+            "7292".to_string(),
+        );
+        assert_eq!(dual_stack.local_class, Some(AddressClass::Private));
+        assert_eq!(dual_stack.foreign_class, Some(AddressClass::Public));
+        assert!(dual_stack.local_mapped && dual_stack.foreign_mapped);
+        assert!(dual_stack.foreign_class.unwrap().is_external());
+
+        let udp = Connection::new(
+            ConnectionType::Udp,
+            "[fe80::d780:dafb:bd15:aba8%3]:1900".to_string(),
+            "*:*".to_string(),
+            ConnectionState::Close,
+            "3920".to_string(),
+        );
+        assert_eq!(udp.local_class, Some(AddressClass::LinkLocal));
+        assert_eq!(udp.foreign_class, None);
+    }
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let input = "2291 (Web Content) S 2110 2031 2031 0 -1 4194560 47390 0 0 0 \
+                     1033 266 0 0 20 0 27 0 9352 2674352128 51520 18446744073709551615";
+        let process = parse_proc_stat(input).unwrap();
+        assert_eq!(process.pid, "2291");
+        assert_eq!(process.image_name, "Web Content");
+        assert_eq!(process.parent_pid.as_deref(), Some("2110"));
+        assert_eq!(process.session_number, "2031");
+
+        let process = parse_proc_stat("41 (a) b) R 1 41 41 0").unwrap();
+        assert_eq!(process.image_name, "a) b");
+        assert_eq!(process.parent_pid.as_deref(), Some("1"));
+
+        assert!(parse_proc_stat("41 (truncated").is_none());
+    }
+
     #[test]
     fn test_parse_processes() {
         let input = r#"
@@ -1935,4 +3540,322 @@ mod tests {
 
         assert_eq!(processes.len(), 200);
     }
+
+    #[test]
+    fn test_parse_processes_verbose() {
+        let input = r#"
+        "Image Name","PID","Session Name","Session#","Mem Usage","Status","User Name","CPU Time","Window Title"
+        "System","4","Services","0","1,024 K","Unknown","N/A","0:12:01","N/A"
+        "explorer.exe","6120","RDP-Tcp#3","2","98,304 K","Running","CORP\alice","0:00:41","Program Manager"
+        "cmd.exe","7001","Console","1","4,096 K","Running","CORP\bob","0:00:00","C:\Windows\system32\cmd.exe - ""quoted"""
+        "#;
+
+        let processes = parse_processes(input);
+
+        assert_eq!(processes.len(), 3);
+        assert_eq!(processes[0].user, None);
+        assert_eq!(processes[1].image_name, "explorer.exe");
+        assert_eq!(processes[1].session_name, "RDP-Tcp#3");
+        assert_eq!(processes[1].session_number, "2");
+        assert_eq!(processes[1].user.as_deref(), Some("CORP\\alice"));
+        assert_eq!(processes[2].user.as_deref(), Some("CORP\\bob"));
+
+        // without /v there is no user
+        let process =
+            parse_process_line(r#""svchost.exe","1056","Services","0","28,256 K""#).unwrap();
+        assert_eq!(process.pid, "1056");
+        assert_eq!(process.user, None);
+    }
+
+    #[test]
+    fn test_parse_proc_net_route() {
+        let input =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+docker0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\t0\t0\t0
+";
+        let routes = parse_proc_net_route(input);
+
+        assert_eq!(routes.len(), 3);
+        assert_eq!(
+            routes[0],
+            Route {
+                destination: "0.0.0.0".parse().unwrap(),
+                prefix_len: 0,
+                gateway: Some("192.168.1.1".parse().unwrap()),
+                interface: "eth0".to_string(),
+                metric: 100,
+            }
+        );
+        assert_eq!(
+            routes[1].destination,
+            "192.168.1.0".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(routes[1].prefix_len, 24);
+        assert_eq!(routes[1].gateway, None);
+        assert_eq!(
+            routes[2].destination,
+            "172.17.0.0".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(routes[2].prefix_len, 16);
+    }
+
+    #[test]
+    fn test_parse_proc_net_ipv6_route() {
+        let input = "00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003     eth0
+fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001     eth0
+00000000000000000000000000000001 80 00000000000000000000000000000000 00 00000000000000000000000000000000 00000000 00000002 00000000 80200001       lo
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200       lo
+";
+        let routes = parse_proc_net_ipv6_route(input);
+
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].destination, "::".parse::<IpAddr>().unwrap());
+        assert_eq!(routes[0].gateway, Some("fe80::1".parse().unwrap()));
+        assert_eq!(routes[0].metric, 1024);
+        assert_eq!(routes[1].destination, "fe80::".parse::<IpAddr>().unwrap());
+        assert_eq!(routes[1].prefix_len, 64);
+        assert_eq!(routes[1].gateway, None);
+        assert_eq!(routes[2].interface, "lo");
+        assert_eq!(routes[2].prefix_len, 128);
+    }
+
+    #[test]
+    fn test_parse_route_print() {
+        let input = r#"
+===========================================================================
+Interface List
+ 14...a0 b5 49 12 34 56 ......Intel(R) Wi-Fi 6 AX201 160MHz
+  1...........................Software Loopback Interface 1
+===========================================================================
+
+IPv4 Route Table
+===========================================================================
+Active Routes:
+Network Destination        Netmask          Gateway       Interface  Metric
+          0.0.0.0          0.0.0.0      192.168.1.1     192.168.1.20     35
+        127.0.0.0        255.0.0.0         On-link         127.0.0.1    331
+      192.168.1.0    255.255.255.0         On-link      192.168.1.20    291
+===========================================================================
+Persistent Routes:
+  Network Address          Netmask  Gateway Address  Metric
+          0.0.0.0          0.0.0.0      192.168.1.1  Default
+===========================================================================
+
+IPv6 Route Table
+===========================================================================
+Active Routes:
+ If Metric Network Destination      Gateway
+ 14    291 ::/0                     fe80::1
+  1    331 ::1/128                  On-link
+ 14    291 2a02:8108:1234:5678:9abc:def0:1234:5678/128
+                                    On-link
+===========================================================================
+Persistent Routes:
+  None
+"#;
+        let routes = parse_route_print(input);
+
+        assert_eq!(routes.len(), 6);
+        assert_eq!(routes[0].gateway, Some("192.168.1.1".parse().unwrap()));
+        assert_eq!(routes[0].interface, "192.168.1.20");
+        assert_eq!(routes[0].metric, 35);
+        assert_eq!(routes[1].prefix_len, 8);
+        assert_eq!(routes[1].gateway, None);
+        assert_eq!(routes[3].destination, "::".parse::<IpAddr>().unwrap());
+        assert_eq!(routes[3].gateway, Some("fe80::1".parse().unwrap()));
+        assert_eq!(routes[3].interface, "14");
+        assert_eq!(routes[5].prefix_len, 128);
+        assert_eq!(routes[5].gateway, None);
+        assert_eq!(routes[5].metric, 291);
+    }
+
+    #[test]
+    fn test_route_for() {
+        let mut routes = parse_proc_net_route(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+wlan0\t00000000\t0102A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+docker0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\t0\t0\t0
+",
+        );
+        routes.extend(parse_proc_net_ipv6_route(
+            "00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003     eth0
+00000000000000000000000000000001 80 00000000000000000000000000000000 00 00000000000000000000000000000000 00000000 00000002 00000000 80200001       lo
+",
+        ));
+
+        let route =
+            |ip: &str| route_for(&routes, &ip.parse().unwrap()).map(|r| r.interface.as_str());
+        assert_eq!(route("8.8.8.8"), Some("eth0"));
+        assert_eq!(
+            route_for(&routes, &"8.8.8.8".parse().unwrap())
+                .unwrap()
+                .metric,
+            100
+        );
+        assert_eq!(route("192.168.1.50"), Some("eth0"));
+        assert_eq!(route("172.17.0.2"), Some("docker0"));
+        assert_eq!(route("2001:db8::1"), Some("eth0"));
+        assert_eq!(route("::1"), Some("lo"));
+        assert_eq!(route_for(&routes[2..4], &"8.8.8.8".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_parse_proc_net_arp() {
+        let input = "IP address       HW type     Flags       HW address            Mask     Device
+192.168.1.1      0x1         0x2         a0:b5:49:12:34:56     *        eth0
+192.168.1.77     0x1         0x0         00:00:00:00:00:00     *        eth0
+10.0.0.5         0x1         0x6         00:11:22:33:44:55     *        eth1
+";
+        let neighbors = parse_proc_net_arp(input);
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(
+            neighbors[0],
+            Neighbor {
+                address: "192.168.1.1".parse().unwrap(),
+                mac: Some("a0:b5:49:12:34:56".to_string()),
+                interface: "eth0".to_string(),
+                state: NeighborState::Dynamic,
+            }
+        );
+        assert_eq!(neighbors[1].mac, None);
+        assert_eq!(neighbors[1].state, NeighborState::Incomplete);
+        assert_eq!(neighbors[2].state, NeighborState::Static);
+    }
+
+    #[test]
+    fn test_parse_ip_neigh() {
+        let input = "fe80::1 dev eth0 lladdr a0:b5:49:12:34:56 router REACHABLE
+2a02:8108::77 dev eth0 FAILED
+fe80::5 dev eth0 lladdr 00:11:22:33:44:66 STALE
+";
+        let neighbors = parse_ip_neigh(input);
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(neighbors[0].mac.as_deref(), Some("a0:b5:49:12:34:56"));
+        assert_eq!(neighbors[0].interface, "eth0");
+        assert_eq!(neighbors[0].state, NeighborState::Dynamic);
+        assert_eq!(neighbors[1].mac, None);
+        assert_eq!(neighbors[1].state, NeighborState::Incomplete);
+        assert_eq!(neighbors[2].state, NeighborState::Dynamic);
+    }
+
+    #[test]
+    fn test_parse_arp() {
+        let input = r#"
+Interface: 192.168.1.20 --- 0xe
+  Internet Address      Physical Address      Type
+  192.168.1.1           a0-b5-49-12-34-56     dynamic
+  192.168.1.255         ff-ff-ff-ff-ff-ff     static
+
+Interface: 172.24.0.1 --- 0x2a
+  Internet Address      Physical Address      Type
+  172.24.15.255         ff-ff-ff-ff-ff-ff     static
+"#;
+        let neighbors = parse_arp(input);
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(neighbors[0].interface, "192.168.1.20");
+        assert_eq!(neighbors[0].mac.as_deref(), Some("a0:b5:49:12:34:56"));
+        assert_eq!(neighbors[0].state, NeighborState::Dynamic);
+        assert_eq!(neighbors[1].state, NeighborState::Static);
+        assert_eq!(neighbors[2].interface, "172.24.0.1");
+
+        let input = r#"
+Interface 14: Wi-Fi
+
+
+Internet Address                              Physical Address   Type
+--------------------------------------------  -----------------  -----------
+fe80::1                                       a0-b5-49-12-34-56  Reachable (Router)
+ff02::1                                       33-33-00-00-00-01  Permanent
+fe80::77                                      00-00-00-00-00-00  Unreachable
+"#;
+        let neighbors = parse_arp(input);
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(neighbors[0].interface, "Wi-Fi");
+        assert_eq!(neighbors[0].state, NeighborState::Dynamic);
+        assert_eq!(neighbors[1].state, NeighborState::Static);
+        assert_eq!(neighbors[2].mac, None);
+        assert_eq!(neighbors[2].state, NeighborState::Incomplete);
+    }
+
+    #[test]
+    fn test_parse_proc_net_netstat() {
+        let input = "TcpExt: SyncookiesSent SyncookiesRecv SyncookiesFailed ListenOverflows ListenDrops TCPTimeouts
+TcpExt: 0 0 3 17 17 1204
+IpExt: InNoRoutes InTruncatedPkts InOctets OutOctets
+IpExt: 2 0 98231221 12873311
+MPTcpExt: MPCapableSYNRX
+MPTcpExt: 0
+";
+        let extended = parse_proc_net_netstat(input);
+
+        assert_eq!(extended.0.len(), 3);
+        assert_eq!(extended.get("TcpExt", "ListenOverflows"), Some(17));
+        assert_eq!(extended.get("TcpExt", "TCPTimeouts"), Some(1204));
+        assert_eq!(extended.get("IpExt", "InOctets"), Some(98231221));
+        assert_eq!(extended.get("MPTcpExt", "MPCapableSYNRX"), Some(0));
+        assert_eq!(extended.get("TcpExt", "InOctets"), None);
+
+        let mut stats = parse_stats("");
+        assert!(serde_json::to_value(&stats)
+            .unwrap()
+            .get("extended")
+            .is_none());
+        stats.set_extended(extended);
+        let value = serde_json::to_value(&stats).unwrap();
+        assert_eq!(value["extended"]["TcpExt"]["ListenOverflows"], 17);
+
+        assert!(extended_counter_description("TcpExt", "ListenOverflows").is_some());
+        assert_eq!(extended_counter_description("TcpExt", "Nope"), None);
+    }
+
+    #[test]
+    fn test_parse_proc_net_stats() {
+        let files = [
+            (
+                "snmp",
+                "Tcp: RtoAlgorithm ActiveOpens RetransSegs\nTcp: 1 64644 24199\n".to_string(),
+            ),
+            ("dev", "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 8302212   79054    0    0    0     0          0         0  8302212   79054    0    0    0     0       0          0
+  eth0:1893449342 1527811    3   12    0     0          0      1204 103441125  784112    0    1    0     0       0          0
+".to_string()),
+            (
+                "netstat",
+                "TcpExt: ListenOverflows\nTcpExt: 17\n".to_string(),
+            ),
+        ];
+        let input = format_proc_net_files(&files);
+        assert!(input.starts_with("==> /proc/net/snmp <==\nTcp: "));
+        assert_eq!(split_proc_net_files(&input), files);
+
+        let (snapshot, extended) = parse_proc_net_stats(&input);
+        assert_eq!(snapshot.sections[0].name, "interface");
+        assert_eq!(
+            snapshot.get("interface", "errors"),
+            Some(StatValue::Pair(NetworkData {
+                received: 3,
+                sent: 0
+            }))
+        );
+        assert_eq!(
+            snapshot.get("tcp_ipv4", "segments_retransmitted"),
+            Some(StatValue::Count(24199))
+        );
+        // no snmp6 in the input
+        assert!(snapshot.section("ipv6").is_none());
+        assert_eq!(extended.get("TcpExt", "ListenOverflows"), Some(17));
+
+        let (snapshot, extended) = parse_proc_net_stats("");
+        assert!(snapshot.sections.is_empty());
+        assert!(extended.0.is_empty());
+    }
 }
@@ -0,0 +1,214 @@
+//! Inventory of everything that accepts traffic on this machine: TCP sockets
+//! in LISTEN and unconnected (bound) UDP sockets.
+//!
+//! Dual-stack servers usually show up twice, once bound to `0.0.0.0` and once
+//! to `[::]` (or `127.0.0.1` and `[::1]`); those are folded into one entry.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use serde::Serialize;
+
+use crate::services::ServiceTable;
+use crate::{classify_address, AddressClass, Connection, ConnectionState, ConnectionType, Process};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum BindScope {
+    /// Bound to `0.0.0.0` or `[::]`.
+    AllInterfaces,
+    LoopbackOnly,
+    /// Bound to one specific (non-loopback) address.
+    Specific,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Listener {
+    pub connection_type: ConnectionType,
+    pub port: u16,
+    pub scope: BindScope,
+    /// Every local address this listener was seen on, e.g. `0.0.0.0` and `::`.
+    pub addresses: Vec<IpAddr>,
+    pub ipv4: bool,
+    pub ipv6: bool,
+    pub pid: String,
+    pub image_name: Option<String>,
+    pub service: Option<String>,
+}
+
+pub fn bind_scope(ip: &IpAddr) -> BindScope {
+    match classify_address(ip) {
+        AddressClass::Unspecified => BindScope::AllInterfaces,
+        AddressClass::Loopback => BindScope::LoopbackOnly,
+        _ => BindScope::Specific,
+    }
+}
+
+fn is_listening(connection: &Connection) -> bool {
+    match connection.connection_type {
+        ConnectionType::Tcp => connection.state == ConnectionState::Listen,
+        // a UDP socket with a peer is connected, not bound
+        ConnectionType::Udp => connection
+            .foreign_endpoint()
+            .is_none_or(|(ip, _)| ip.is_unspecified()),
+    }
+}
+
+/// Builds the inventory from a connection snapshot, sorted by port. `processes`
+/// is only used to put a name on each PID.
+pub fn listening_inventory(
+    connections: &[Connection],
+    processes: &[Process],
+    services: &ServiceTable,
+) -> Vec<Listener> {
+    let names: HashMap<&str, &str> = processes
+        .iter()
+        .map(|process| (process.pid.as_str(), process.image_name.as_str()))
+        .collect();
+
+    let mut listeners: Vec<Listener> = Vec::new();
+    // (protocol, port, pid, scope, specific address) -> index into `listeners`
+    let mut index = HashMap::new();
+
+    for connection in connections.iter().filter(|c| is_listening(c)) {
+        let Some((ip, Some(port))) = connection.local_endpoint() else {
+            continue;
+        };
+
+        let scope = bind_scope(&ip);
+        let specific = (scope == BindScope::Specific).then_some(ip);
+        let key = (
+            connection.connection_type,
+            port,
+            connection.pid.clone(),
+            scope,
+            specific,
+        );
+
+        let listener = match index.get(&key) {
+            Some(&i) => &mut listeners[i],
+            None => {
+                index.insert(key, listeners.len());
+                listeners.push(Listener {
+                    connection_type: connection.connection_type,
+                    port,
+                    scope,
+                    addresses: Vec::new(),
+                    ipv4: false,
+                    ipv6: false,
+                    pid: connection.pid.clone(),
+                    image_name: names
+                        .get(connection.pid.as_str())
+                        .map(|name| name.to_string()),
+                    service: services
+                        .lookup(port, connection.connection_type)
+                        .map(str::to_string),
+                });
+                listeners.last_mut().unwrap()
+            }
+        };
+
+        match ip {
+            IpAddr::V4(_) => listener.ipv4 = true,
+            IpAddr::V6(_) => listener.ipv6 = true,
+        }
+        if !listener.addresses.contains(&ip) {
+            listener.addresses.push(ip);
+        }
+    }
+
+    listeners.sort_by(|a, b| {
+        (a.port, a.connection_type == ConnectionType::Udp, &a.pid).cmp(&(
+            b.port,
+            b.connection_type == ConnectionType::Udp,
+            &b.pid,
+        ))
+    });
+    listeners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_connections;
+
+    #[test]
+    fn test_listening_inventory_windows() {
+        let input = r#"
+        Active Connections
+
+        Proto  Local Address          Foreign Address        State           PID
+        TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1192
+        TCP    0.0.0.0:5432           0.0.0.0:0              LISTENING       7292
+        TCP    127.0.0.1:6463         0.0.0.0:0              LISTENING       14488
+        TCP    192.168.100.14:139     0.0.0.0:0              LISTENING       4
+        TCP    192.168.100.14:57793   40.115.3.253:443       ESTABLISHED     5236
+        TCP    [::]:135               [::]:0                 LISTENING       1192
+        TCP    [::]:5432              [::]:0                 LISTENING       7292
+        TCP    [::1]:1420             [::]:0                 LISTENING       11524
+        TCP    [::1]:1420             [::1]:58750            ESTABLISHED     11524
+        UDP    0.0.0.0:5353           *:*                                    3604
+        UDP    0.0.0.0:5353           *:*                                    3604
+        UDP    [::]:5353              *:*                                    3604
+        UDP    192.168.100.14:137     *:*                                    4
+        UDP    [fe80::d780:dafb:bd15:aba8%3]:1900  *:*                       3920"#;
+
+        let connections = parse_connections(input);
+        let processes = vec![Process::new("postgres.exe".to_string(), "7292".to_string())];
+
+        let listeners = listening_inventory(&connections, &processes, &ServiceTable::bundled());
+        let summary: Vec<_> = listeners
+            .iter()
+            .map(|l| (l.port, l.connection_type, l.scope, l.ipv4, l.ipv6))
+            .collect();
+
+        use BindScope::*;
+        use ConnectionType::*;
+        assert_eq!(
+            summary,
+            vec![
+                (135, Tcp, AllInterfaces, true, true),
+                (137, Udp, Specific, true, false),
+                (139, Tcp, Specific, true, false),
+                (1420, Tcp, LoopbackOnly, false, true),
+                (1900, Udp, Specific, false, true),
+                (5353, Udp, AllInterfaces, true, true),
+                (5432, Tcp, AllInterfaces, true, true),
+                (6463, Tcp, LoopbackOnly, true, false),
+            ]
+        );
+
+        let postgres = listeners.iter().find(|l| l.port == 5432).unwrap();
+        assert_eq!(postgres.image_name.as_deref(), Some("postgres.exe"));
+        assert_eq!(postgres.service.as_deref(), Some("postgresql"));
+        assert_eq!(
+            postgres.addresses,
+            vec!["0.0.0.0".parse::<IpAddr>().unwrap(), "::".parse().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_listening_inventory_linux() {
+        let input = r#"
+        Active Internet connections (servers and established)
+        Proto Recv-Q Send-Q Local Address           Foreign Address         State       PID/Program name
+        tcp        0      0 127.0.0.53:53           0.0.0.0:*               LISTEN      612/systemd-resolve
+        tcp        0      0 0.0.0.0:22              0.0.0.0:*               LISTEN      801/sshd
+        tcp        0   2896 192.168.1.20:22         192.168.1.5:50514       ESTABLISHED 1834/sshd: user
+        tcp6       0      0 :::22                   :::*                    LISTEN      801/sshd
+        udp        0      0 127.0.0.53:53           0.0.0.0:*                           612/systemd-resolve
+        udp        0      0 192.168.1.20:58422      1.1.1.1:53              ESTABLISHED 2291/firefox"#;
+
+        let connections = parse_connections(input);
+        let listeners = listening_inventory(&connections, &[], &ServiceTable::bundled());
+
+        assert_eq!(listeners.len(), 3);
+        assert_eq!(listeners[0].port, 22);
+        assert_eq!(listeners[0].scope, BindScope::AllInterfaces);
+        assert!(listeners[0].ipv4 && listeners[0].ipv6);
+        assert_eq!(listeners[0].service.as_deref(), Some("ssh"));
+        assert_eq!(listeners[1].connection_type, ConnectionType::Tcp);
+        assert_eq!(listeners[1].scope, BindScope::LoopbackOnly);
+        assert_eq!(listeners[2].connection_type, ConnectionType::Udp);
+        assert_eq!(listeners[2].service.as_deref(), Some("domain"));
+    }
+}
@@ -1,11 +1,63 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use net_process::{group_connections_by_pid, parse_connections, parse_processes, parse_stats};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use net_process::actions::{self, ActionError, AuditLog};
+use net_process::containers::group_connections_by_container;
+use net_process::dns::{DnsCache, SystemLookup};
+use net_process::geoip::{GeoIpEnricher, MmdbLookup};
+use net_process::import::{self, format_report, Import};
+use net_process::listening::listening_inventory;
+use net_process::port_lookup::{find_by_port, format_port_owners, parse_protocol};
+use net_process::process_tree::build_process_tree;
+use net_process::recording::{Recorder, Recording, Replay, Source};
+use net_process::rules::{log_alerts, parse_rules, Rule, RuleEngine};
+use net_process::services::ServiceTable;
+use net_process::thresholds::{parse_thresholds, Threshold, ThresholdEngine};
+use net_process::users::{group_processes, GroupBy, ProcessFilter};
+use net_process::{
+    group_connections_by_pid, group_connections_by_service, parse_proc_net_stats,
+    parse_stats_snapshot, route_for, split_address, Connection, Neighbor, Process, Route,
+    EXTENDED_COUNTER_DESCRIPTIONS,
+};
+#[cfg(not(target_os = "linux"))]
 use tauri::api::process::{Command, CommandEvent};
+use tauri::Manager;
 
 #[tauri::command]
-async fn net_stat() -> String {
+async fn net_stat(
+    app: tauri::AppHandle,
+    alerting: tauri::State<'_, Alerting>,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    // recordings from Linux have /proc/net samples, all others `netstat -s`
+    let stats = match (
+        sampler.replayed(Source::ProcNet),
+        sampler.replayed(Source::Stats),
+    ) {
+        (Some(Ok(input)), _) => proc_net_stats(&input),
+        (_, Some(input)) => netstat_stats(&input?),
+        (_, None) => native_stats(&sampler).await?,
+    };
+    check_thresholds(&app, &alerting, &stats);
+    Ok(stats.to_string())
+}
+
+/// net-tools' `netstat -s` has none of the Windows sections, the same
+/// counters come straight from /proc instead.
+#[cfg(target_os = "linux")]
+async fn native_stats(sampler: &Sampler) -> Result<serde_json::Value, String> {
+    let input = net_process::procfs::stats_files().map_err(|err| err.to_string())?;
+    sampler.record(Source::ProcNet, &input);
+    Ok(proc_net_stats(&input))
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn native_stats(sampler: &Sampler) -> Result<serde_json::Value, String> {
     // TODO: use cmd spawned from tauri instead of creating a new one
     //       each time this function is called
     let (mut rx, _child) = Command::new("netstat")
@@ -23,18 +75,174 @@ async fn net_stat() -> String {
         };
         input.push_str(line.as_str());
     }
-    let stats = parse_stats(input.as_str());
-    serde_json::to_string(&stats).unwrap()
+    sampler.record(Source::Stats, &input);
+    Ok(netstat_stats(&input))
+}
+
+/// The snapshot rather than `NetworkStatistics`, so that counters without a
+/// struct field still reach the UI.
+fn netstat_stats(input: &str) -> serde_json::Value {
+    serde_json::to_value(parse_stats_snapshot(input)).unwrap()
+}
+
+fn proc_net_stats(input: &str) -> serde_json::Value {
+    let (snapshot, extended) = parse_proc_net_stats(input);
+    let mut stats = serde_json::to_value(snapshot).unwrap();
+    stats["extended"] = serde_json::to_value(extended).unwrap();
+    stats
+}
+
+#[tauri::command]
+fn extended_counter_descriptions() -> String {
+    let mut descriptions: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+    for &(section, name, description) in EXTENDED_COUNTER_DESCRIPTIONS {
+        descriptions
+            .entry(section)
+            .or_default()
+            .insert(name, description);
+    }
+    serde_json::to_string(&descriptions).unwrap()
+}
+
+/// Units and descriptions of the `net_stat` counters, by section and name.
+#[tauri::command]
+fn stat_fields() -> String {
+    serde_json::to_string(&net_process::stat_fields()).unwrap()
 }
 
+/// Counters of every network adapter, next to the `netstat -e` aggregate in
+/// `net_stat`.
 #[tauri::command]
-async fn net_connections() -> String {
+fn net_interfaces() -> Result<String, String> {
+    let interfaces = net_process::interfaces::interfaces().map_err(|err| err.to_string())?;
+    Ok(serde_json::to_string(&interfaces).unwrap())
+}
+
+type Dns = DnsCache<SystemLookup>;
+type GeoIp = Mutex<Option<GeoIpEnricher<MmdbLookup>>>;
+
+/// Where the command outputs behind connections, processes and statistics
+/// come from: the system, optionally recorded, or a replayed recording.
+/// Imported files take precedence over both for connections and processes.
+struct Sampler {
+    backend: Backend,
+    import: Mutex<Import>,
+    replay: Option<Replay>,
+    recorder: Option<Recorder>,
+}
+
+impl Sampler {
+    /// `rows` of the imported files, `None` when no file is open.
+    fn imported<T>(&self, rows: impl FnOnce(&Import) -> Vec<T>) -> Option<Vec<T>> {
+        let import = self.import.lock().unwrap();
+        (!import.is_empty()).then(|| rows(&import))
+    }
+
+    /// The recorded output for `source` when replaying, `None` otherwise.
+    fn replayed(&self, source: Source) -> Option<Result<String, String>> {
+        let replay = self.replay.as_ref()?;
+        Some(replay.output(source).map(str::to_string).ok_or_else(|| {
+            format!(
+                "the recording has no {} samples",
+                format!("{:?}", source).to_lowercase()
+            )
+        }))
+    }
+
+    fn record(&self, source: Source, output: &str) {
+        if let Some(recorder) = &self.recorder {
+            if let Err(err) = recorder.record(source, output) {
+                eprintln!("failed to record sample: {}", err);
+            }
+        }
+    }
+}
+
+/// Where live connections come from. `Native` is the platform's own API,
+/// the others run a command, for hosts where that API is locked down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Native,
+    /// `ss -tuanpeo`, Linux only.
+    Ss,
+    /// `lsof -i -n -P`, any Unix. Also the source of processes, so only
+    /// those holding a socket are listed.
+    Lsof,
+}
+
+fn parse_backend(name: &str) -> Option<Backend> {
+    match name {
+        "native" => Some(Backend::Native),
+        "ss" => Some(Backend::Ss),
+        "lsof" => Some(Backend::Lsof),
+        _ => None,
+    }
+}
+
+async fn collect_connections(sampler: &Sampler) -> Result<Vec<Connection>, String> {
+    if let Some(connections) = sampler.imported(|import| import.connections.clone()) {
+        return Ok(connections);
+    }
+    match sampler.replayed(Source::Connections) {
+        // the recording may come from any backend
+        Some(input) => Ok(import::import_text("recording", &input?)
+            .map(|file| file.connections)
+            .unwrap_or_default()),
+        None => system_connections(sampler).await,
+    }
+}
+
+async fn system_connections(sampler: &Sampler) -> Result<Vec<Connection>, String> {
+    match sampler.backend {
+        Backend::Native => native_connections(sampler).await,
+        Backend::Ss => {
+            let input = command_output("ss", &["-tuanpeo"])?;
+            sampler.record(Source::Connections, &input);
+            Ok(net_process::parse_ss(&input))
+        }
+        Backend::Lsof => {
+            let input = lsof_output()?;
+            sampler.record(Source::Connections, &input);
+            Ok(net_process::parse_lsof(&input).0)
+        }
+    }
+}
+
+/// `lsof -i -n -P`, which exits with 1 when there is no socket at all.
+fn lsof_output() -> Result<String, String> {
+    let output = std::process::Command::new("lsof")
+        .args(["-i", "-n", "-P"])
+        .output()
+        .map_err(|err| format!("lsof: {}", err))?;
+    if !output.status.success() && !output.stderr.is_empty() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Not recorded, there is no command output behind it; `sampler_from_args`
+/// does not record with this backend.
+#[cfg(target_os = "linux")]
+async fn native_connections(_sampler: &Sampler) -> Result<Vec<Connection>, String> {
+    // the netlink round-trip and the walk over every namespace's /proc block
+    tauri::async_runtime::spawn_blocking(|| {
+        // sock_diag can be blocked (e.g. by seccomp), /proc/net is the fallback
+        net_process::sock_diag::connections()
+            .or_else(|_| net_process::procfs::connections())
+            .map_err(|err| err.to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn native_connections(sampler: &Sampler) -> Result<Vec<Connection>, String> {
     // TODO: use cmd spawned from tauri instead of creating a new one
     //       each time this function is called
     let (mut rx, _child) = Command::new("netstat")
         .args(["-ano"])
         .spawn()
-        .expect("failed to execute process");
+        .map_err(|err| err.to_string())?;
 
     let mut input = String::new();
     while let Some(event) = rx.recv().await {
@@ -46,13 +254,250 @@ async fn net_connections() -> String {
         };
         input.push_str(line.as_str());
     }
-    let connections = parse_connections(input.as_str());
+    sampler.record(Source::Connections, &input);
+    Ok(net_process::parse_connections(input.as_str()))
+}
+
+struct Alerting {
+    engine: Mutex<RuleEngine>,
+    thresholds: Mutex<ThresholdEngine>,
+    rules_path: PathBuf,
+    log_path: PathBuf,
+}
+
+/// Rules and thresholds from `path`, none when the file does not exist.
+fn read_rules(path: &Path) -> Result<(Vec<Rule>, Vec<Threshold>), String> {
+    if !path.exists() {
+        return Ok(Default::default());
+    }
+    let input = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    parse_rules(&input)
+        .and_then(|rules| Ok((rules, parse_thresholds(&input)?)))
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+fn notify(app: &tauri::AppHandle, title: &str, body: String) {
+    let notification =
+        tauri::api::notification::Notification::new(&app.config().tauri.bundle.identifier)
+            .title(title)
+            .body(body);
+    if let Err(err) = notification.show() {
+        eprintln!("failed to show notification: {}", err);
+    }
+}
+
+/// Runs the alerting rules against the connections that are new since the
+/// last call, then notifies and logs every match.
+async fn raise_alerts(app: &tauri::AppHandle, alerting: &Alerting, connections: &[Connection]) {
+    let needs_names = alerting
+        .engine
+        .lock()
+        .unwrap()
+        .rules()
+        .iter()
+        .any(|rule| rule.process.is_some());
+
+    // only ask for names when a rule needs them, and without the engine locked
+    let image_names = if needs_names {
+        spawn_collect_processes(app)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|process| (process.pid, process.image_name))
+            .collect()
+    } else {
+        Default::default()
+    };
+
+    let alerts = alerting
+        .engine
+        .lock()
+        .unwrap()
+        .evaluate(connections, &image_names);
+
+    for alert in &alerts {
+        notify(app, &alert.rule, alert.summary());
+    }
+    if let Err(err) = log_alerts(&alerting.log_path, &alerts) {
+        eprintln!("failed to write alert log: {}", err);
+    }
+}
+
+/// Feeds a statistics sample to the thresholds. Every change is sent to the
+/// UI as a `threshold-alert` event and logged, crossings also notify.
+fn check_thresholds(app: &tauri::AppHandle, alerting: &Alerting, stats: &serde_json::Value) {
+    let events = alerting
+        .thresholds
+        .lock()
+        .unwrap()
+        .evaluate(stats, Instant::now());
+
+    for event in &events {
+        if event.crossed {
+            notify(app, &event.name, event.summary());
+        }
+        if let Err(err) = app.emit_all("threshold-alert", event) {
+            eprintln!("failed to emit threshold alert: {}", err);
+        }
+    }
+    if let Err(err) = log_alerts(&alerting.log_path, &events) {
+        eprintln!("failed to write alert log: {}", err);
+    }
+}
+
+#[tauri::command]
+async fn net_connections(
+    app: tauri::AppHandle,
+    dns: tauri::State<'_, Dns>,
+    services: tauri::State<'_, ServiceTable>,
+    geoip: tauri::State<'_, GeoIp>,
+    alerting: tauri::State<'_, Alerting>,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let mut connections = collect_connections(&sampler).await?;
+    dns.annotate(&mut connections);
+    services.annotate(&mut connections);
+    if let Some(geoip) = geoip.lock().unwrap().as_ref() {
+        geoip.annotate(&mut connections);
+    }
+    raise_alerts(&app, &alerting, &connections).await;
     let grouped = group_connections_by_pid(connections);
-    String::from_utf8(serde_json::to_string(&grouped).unwrap().into_bytes()).unwrap()
+    Ok(String::from_utf8(serde_json::to_string(&grouped).unwrap().into_bytes()).unwrap())
+}
+
+#[tauri::command]
+async fn net_connections_by_service(
+    services: tauri::State<'_, ServiceTable>,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let mut connections = collect_connections(&sampler).await?;
+    services.annotate(&mut connections);
+    let grouped = group_connections_by_service(connections);
+    Ok(serde_json::to_string(&grouped).unwrap())
+}
+
+/// Connections by container ID, `host` for those outside of any.
+#[tauri::command]
+async fn net_connections_by_container(
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let connections = collect_connections(&sampler).await?;
+    let grouped = group_connections_by_container(connections);
+    Ok(serde_json::to_string(&grouped).unwrap())
 }
 
+/// Enables GeoIP enrichment from the given `.mmdb` files, or disables it when
+/// `paths` is empty.
 #[tauri::command]
-fn processes() -> String {
+fn set_geoip_databases(paths: Vec<String>, geoip: tauri::State<'_, GeoIp>) -> Result<(), String> {
+    let enricher = if paths.is_empty() {
+        None
+    } else {
+        Some(GeoIpEnricher::new(MmdbLookup::open(&paths)?))
+    };
+    *geoip.lock().unwrap() = enricher;
+    Ok(())
+}
+
+/// Re-reads the rules file, returns how many rules and thresholds are active.
+#[tauri::command]
+fn reload_rules(alerting: tauri::State<'_, Alerting>) -> Result<usize, String> {
+    let (rules, thresholds) = read_rules(&alerting.rules_path)?;
+    let count = rules.len() + thresholds.len();
+    alerting.engine.lock().unwrap().set_rules(rules);
+    alerting
+        .thresholds
+        .lock()
+        .unwrap()
+        .set_thresholds(thresholds);
+    Ok(count)
+}
+
+/// How long a process gets to exit after SIGTERM before it is killed.
+const TERMINATE_GRACE: Duration = Duration::from_secs(3);
+
+async fn run_action<F>(audit: &AuditLog, action: &str, target: &str, f: F) -> Result<(), String>
+where
+    F: FnOnce() -> Result<(), ActionError> + Send + 'static,
+{
+    let result = tauri::async_runtime::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|err| Err(ActionError::Failed(err.to_string())));
+    if let Err(err) = audit.record(action, target, &result) {
+        eprintln!("failed to write audit log: {}", err);
+    }
+    result.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+async fn kill_process(pid: u32, audit: tauri::State<'_, AuditLog>) -> Result<(), String> {
+    run_action(&audit, "kill_process", &pid.to_string(), move || {
+        actions::terminate_process(pid, TERMINATE_GRACE)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn close_connection(
+    local_address: String,
+    foreign_address: String,
+    audit: tauri::State<'_, AuditLog>,
+) -> Result<(), String> {
+    let target = format!("{} -> {}", local_address, foreign_address);
+    let local = local_address.parse();
+    let foreign = foreign_address.parse();
+    run_action(&audit, "close_connection", &target, move || {
+        match (local, foreign) {
+            (Ok(local), Ok(foreign)) => actions::close_connection(local, foreign),
+            _ => Err(ActionError::InvalidTarget(format!(
+                "{} -> {}",
+                local_address, foreign_address
+            ))),
+        }
+    })
+    .await
+}
+
+fn collect_processes(sampler: &Sampler) -> Result<Vec<Process>, String> {
+    if let Some(processes) = sampler.imported(|import| import.processes.clone()) {
+        return Ok(processes);
+    }
+    match sampler.replayed(Source::Processes) {
+        Some(input) => Ok(import::import_text("recording", &input?)
+            .map(|file| file.processes)
+            .unwrap_or_default()),
+        None => system_processes(sampler),
+    }
+}
+
+/// `collect_processes` off the async runtime, tasklist and walking /proc
+/// both block.
+async fn spawn_collect_processes(app: &tauri::AppHandle) -> Result<Vec<Process>, String> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || collect_processes(&app.state::<Sampler>()))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+fn system_processes(sampler: &Sampler) -> Result<Vec<Process>, String> {
+    match sampler.backend {
+        Backend::Lsof => {
+            let input = lsof_output()?;
+            sampler.record(Source::Processes, &input);
+            Ok(net_process::parse_lsof(&input).1)
+        }
+        Backend::Native | Backend::Ss => native_processes(sampler),
+    }
+}
+
+/// Not recorded, there is no command output behind it.
+#[cfg(target_os = "linux")]
+fn native_processes(_sampler: &Sampler) -> Result<Vec<Process>, String> {
+    net_process::procfs::processes().map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn native_processes(sampler: &Sampler) -> Result<Vec<Process>, String> {
     // TODO: use cmd spawned from tauri instead of creating a new one
     //       each time this function is called
     let mut cmd = std::process::Command::new("tasklist");
@@ -60,30 +505,378 @@ fn processes() -> String {
     cmd.stdout(std::process::Stdio::piped());
     cmd.stderr(std::process::Stdio::piped());
 
-    let child = cmd.spawn().expect("failed to execute process");
-    let result = child.wait_with_output().expect("failed to wait on child");
-    if result.status.success() {
-        // FIXME: for some reason, the output of tasklist is not utf8,
-        //        and I cannot figure out why. For now, we just convert
-        //        the bytes to chars and then back to a string.
-        let input = result
-            .stdout
-            .iter()
-            .map(|x| char::from(*x))
-            .collect::<String>();
-        let processes = parse_processes(input.as_str());
-        String::from_utf8(serde_json::to_string(&processes).unwrap().into_bytes()).unwrap()
-    } else {
-        String::from_utf8(result.stderr).unwrap()
+    let child = cmd.spawn().map_err(|err| err.to_string())?;
+    let result = child.wait_with_output().map_err(|err| err.to_string())?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).to_string());
+    }
+
+    // FIXME: for some reason, the output of tasklist is not utf8,
+    //        and I cannot figure out why. For now, we just convert
+    //        the bytes to chars and then back to a string.
+    let input = result
+        .stdout
+        .iter()
+        .map(|x| char::from(*x))
+        .collect::<String>();
+    sampler.record(Source::Processes, &input);
+    let processes = net_process::parse_processes(input.as_str());
+
+    #[cfg(windows)]
+    let processes = {
+        let mut processes = processes;
+        if let Ok(parents) = net_process::process_tree::parent_pids() {
+            net_process::process_tree::attach_parent_pids(&mut processes, &parents);
+        }
+        net_process::users::attach_token_users(&mut processes);
+        processes
+    };
+
+    Ok(processes)
+}
+
+fn command_output(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|err| format!("{}: {}", program, err))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn collect_routes() -> Result<Vec<Route>, String> {
+    net_process::procfs::routes().map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_routes() -> Result<Vec<Route>, String> {
+    command_output("route", &["print"]).map(|input| net_process::parse_route_print(&input))
+}
+
+#[cfg(target_os = "linux")]
+fn collect_neighbors() -> Result<Vec<Neighbor>, String> {
+    let mut neighbors = net_process::procfs::arp().map_err(|err| err.to_string())?;
+    // /proc has no NDP cache, iproute2 may not be installed either
+    if let Ok(input) = command_output("ip", &["-6", "neigh", "show"]) {
+        neighbors.extend(net_process::parse_ip_neigh(&input));
+    }
+    Ok(neighbors)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_neighbors() -> Result<Vec<Neighbor>, String> {
+    let mut neighbors = net_process::parse_arp(&command_output("arp", &["-a"])?);
+    if let Ok(input) = command_output("netsh", &["interface", "ipv6", "show", "neighbors"]) {
+        neighbors.extend(net_process::parse_arp(&input));
+    }
+    Ok(neighbors)
+}
+
+#[tauri::command]
+async fn routes() -> Result<String, String> {
+    let routes = tauri::async_runtime::spawn_blocking(collect_routes)
+        .await
+        .map_err(|err| err.to_string())??;
+    Ok(serde_json::to_string(&routes).unwrap())
+}
+
+#[tauri::command]
+async fn neighbors() -> Result<String, String> {
+    let neighbors = tauri::async_runtime::spawn_blocking(collect_neighbors)
+        .await
+        .map_err(|err| err.to_string())??;
+    Ok(serde_json::to_string(&neighbors).unwrap())
+}
+
+/// The route used to reach `address`, either a bare IP or a connection's
+/// foreign address with port. `null` when nothing matches.
+#[tauri::command]
+async fn route_for_address(address: String) -> Result<String, String> {
+    let ip = match address.parse() {
+        Ok(ip) => ip,
+        Err(_) => {
+            split_address(&address)
+                .ok_or(format!("invalid address: {}", address))?
+                .0
+        }
+    };
+    let routes = tauri::async_runtime::spawn_blocking(collect_routes)
+        .await
+        .map_err(|err| err.to_string())??;
+    Ok(serde_json::to_string(&route_for(&routes, &ip)).unwrap())
+}
+
+#[tauri::command]
+async fn processes(app: tauri::AppHandle) -> String {
+    match spawn_collect_processes(&app).await {
+        Ok(processes) => serde_json::to_string(&processes).unwrap(),
+        Err(err) => err,
     }
 }
 
+/// Processes matching `user` and `session` grouped by `by` (`user` or
+/// `session`), with the connections each group holds.
+#[tauri::command]
+async fn process_groups(
+    app: tauri::AppHandle,
+    by: GroupBy,
+    user: Option<String>,
+    session: Option<String>,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let connections = collect_connections(&sampler).await?;
+    let processes = spawn_collect_processes(&app).await?;
+    let filter = ProcessFilter { user, session };
+    let groups = group_processes(processes, connections, by, &filter);
+    Ok(serde_json::to_string(&groups).unwrap())
+}
+
+/// TCP listeners and bound UDP sockets, one entry per dual-stack bind.
+#[tauri::command]
+async fn listening_ports(
+    app: tauri::AppHandle,
+    services: tauri::State<'_, ServiceTable>,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let connections = collect_connections(&sampler).await?;
+    let processes = spawn_collect_processes(&app).await.unwrap_or_default();
+    let listeners = listening_inventory(&connections, &processes, &services);
+    Ok(serde_json::to_string(&listeners).unwrap())
+}
+
+/// Processes with a connection on `port`, local or foreign. `protocol` is
+/// `tcp`, `udp` or absent for both.
+#[tauri::command]
+async fn find_port_owners(
+    app: tauri::AppHandle,
+    port: u16,
+    protocol: Option<String>,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let protocol = match protocol {
+        Some(protocol) => {
+            Some(parse_protocol(&protocol).ok_or(format!("unknown protocol: {}", protocol))?)
+        }
+        None => None,
+    };
+    let connections = collect_connections(&sampler).await?;
+    let processes = spawn_collect_processes(&app).await.unwrap_or_default();
+    let owners = find_by_port(&connections, &processes, port, protocol);
+    Ok(serde_json::to_string(&owners).unwrap())
+}
+
+#[tauri::command]
+async fn process_details(pid: String) -> Result<String, String> {
+    let details = tauri::async_runtime::spawn_blocking(move || {
+        net_process::process_details::process_details(&pid)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string())?;
+    Ok(serde_json::to_string(&details).unwrap())
+}
+
+/// Processes as a parent/child forest, with connection counts and traffic
+/// rolled up to each ancestor.
+#[tauri::command]
+async fn process_tree(
+    app: tauri::AppHandle,
+    sampler: tauri::State<'_, Sampler>,
+) -> Result<String, String> {
+    let processes = spawn_collect_processes(&app).await?;
+    let connections = group_connections_by_pid(collect_connections(&sampler).await?);
+    let tree = build_process_tree(processes, &connections);
+    Ok(serde_json::to_string(&tree).unwrap())
+}
+
+/// Shows the connections or processes in the `netstat`, `ss`, `lsof` or
+/// `tasklist` output at `path` instead of the system's, until `close_files`.
+/// Returns the parse reports of all open files.
+#[tauri::command]
+fn open_file(path: String, sampler: tauri::State<'_, Sampler>) -> Result<String, String> {
+    let file = import::open(Path::new(&path))?;
+    let mut import = sampler.import.lock().unwrap();
+    import.add(file);
+    Ok(serde_json::to_string(&import.reports).unwrap())
+}
+
+#[tauri::command]
+fn imported_files(sampler: tauri::State<'_, Sampler>) -> String {
+    serde_json::to_string(&sampler.import.lock().unwrap().reports).unwrap()
+}
+
+/// Goes back to the system's connections and processes.
+#[tauri::command]
+fn close_files(sampler: tauri::State<'_, Sampler>) {
+    *sampler.import.lock().unwrap() = Import::default();
+}
+
+const USAGE: &str = "usage: net-process [--backend native|ss|lsof] \
+                     [--record <file> | --replay <file> [--speed <n>] | --import <file>...] \
+                     [port <port> [tcp|udp]]\n\n\
+                     --record saves the statistics on Windows and Linux, the connections \
+                     and processes on Windows; elsewhere the connections with --backend ss \
+                     or lsof, the processes with --backend lsof only";
+
+/// Removes `name <value>` from `args`, returning the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(USAGE.to_string());
+    }
+    args.remove(index);
+    Ok(Some(args.remove(index)))
+}
+
+/// `--record <file>` records every command output to `file`, `--replay
+/// <file>` plays such a recording back instead of asking the system,
+/// `--speed` times faster than it was recorded. `--import <file>`, which
+/// can be repeated, opens command output saved elsewhere as with
+/// `open_file`; the parse reports go to stderr. `--backend` picks where live
+/// connections come from.
+///
+/// Statistics are recorded on Windows (`netstat -s -e`) and Linux (the
+/// `/proc/net` files). Connections and processes only when they come from a
+/// command: on Windows always, elsewhere with `--backend ss` (connections)
+/// or `lsof` (both), as the native Linux backend reads `/proc` and netlink.
+fn sampler_from_args(args: &mut Vec<String>) -> Result<Sampler, String> {
+    let backend = match take_option(args, "--backend")? {
+        Some(name) => parse_backend(&name).ok_or(USAGE)?,
+        None => Backend::Native,
+    };
+    let record = take_option(args, "--record")?;
+    let replay = take_option(args, "--replay")?;
+    let mut import = Import::default();
+    while let Some(path) = take_option(args, "--import")? {
+        let file = import::open(Path::new(&path))?;
+        eprint!("{}", format_report(&file.report));
+        import.add(file);
+    }
+    let speed = match take_option(args, "--speed")? {
+        Some(speed) => speed
+            .parse::<f64>()
+            .ok()
+            .filter(|speed| *speed > 0.0)
+            .ok_or(USAGE)?,
+        None => 1.0,
+    };
+
+    let replay = match replay {
+        Some(_) if !import.is_empty() => return Err(USAGE.to_string()),
+        Some(path) => Some(Replay::new(Recording::open(Path::new(&path))?, speed)),
+        None => None,
+    };
+    let recorder = match record {
+        Some(_) if replay.is_some() => return Err(USAGE.to_string()),
+        Some(path) => Some(Recorder::create(&path).map_err(|err| format!("{}: {}", path, err))?),
+        None => None,
+    };
+    Ok(Sampler {
+        backend,
+        import: Mutex::new(import),
+        replay,
+        recorder,
+    })
+}
+
+/// `net-process port 5432 [tcp|udp]` prints who is using the port and exits
+/// instead of opening the window.
+fn run_cli(args: &[String], sampler: &Sampler) -> Result<(), String> {
+    let port = args
+        .first()
+        .and_then(|port| port.parse().ok())
+        .ok_or(USAGE)?;
+    let protocol = match args.get(1) {
+        Some(protocol) => Some(parse_protocol(protocol).ok_or(USAGE)?),
+        None => None,
+    };
+
+    let connections = tauri::async_runtime::block_on(collect_connections(sampler))?;
+    let processes = collect_processes(sampler).unwrap_or_default();
+    let owners = find_by_port(&connections, &processes, port, protocol);
+    print!("{}", format_port_owners(port, &owners));
+    Ok(())
+}
+
 fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let sampler = sampler_from_args(&mut args);
+    if args.first().map(String::as_str) == Some("port") || sampler.is_err() {
+        // release builds use the windows subsystem and start without a console
+        #[cfg(windows)]
+        // SAFETY: AttachConsole has no preconditions.
+        unsafe {
+            windows_sys::Win32::System::Console::AttachConsole(
+                windows_sys::Win32::System::Console::ATTACH_PARENT_PROCESS,
+            );
+        }
+
+        if let Err(err) = sampler.and_then(|sampler| run_cli(&args[1..], &sampler)) {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     tauri::Builder::default()
+        .manage(sampler.unwrap())
+        .manage(Dns::new(SystemLookup, Duration::from_secs(600), 8))
+        .manage(ServiceTable::system())
+        .manage(GeoIp::default())
+        .setup(|app| {
+            let log_dir = app
+                .path_resolver()
+                .app_log_dir()
+                .ok_or("no log directory")?;
+            app.manage(AuditLog::new(log_dir.join("audit.log")));
+
+            let rules_path = app
+                .path_resolver()
+                .app_config_dir()
+                .ok_or("no config directory")?
+                .join("rules.toml");
+            // a broken rules file should not keep the app from starting
+            let (rules, thresholds) = read_rules(&rules_path).unwrap_or_else(|err| {
+                eprintln!("failed to load rules: {}", err);
+                Default::default()
+            });
+            app.manage(Alerting {
+                engine: Mutex::new(RuleEngine::new(rules)),
+                thresholds: Mutex::new(ThresholdEngine::new(thresholds)),
+                rules_path,
+                log_path: log_dir.join("alerts.log"),
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             net_stat,
+            net_interfaces,
+            extended_counter_descriptions,
+            stat_fields,
             net_connections,
-            processes
+            net_connections_by_service,
+            net_connections_by_container,
+            set_geoip_databases,
+            reload_rules,
+            kill_process,
+            close_connection,
+            processes,
+            process_tree,
+            process_groups,
+            process_details,
+            listening_ports,
+            routes,
+            neighbors,
+            route_for_address,
+            find_port_owners,
+            open_file,
+            imported_files,
+            close_files
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,204 @@
+//! "Who is on port N?": finds every connection using a port, on either end,
+//! and groups them by the owning process.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::{Connection, ConnectionType, Process};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PortSide {
+    /// The port is bound locally, i.e. this process owns it.
+    Local,
+    /// The process talks to something on that port.
+    Foreign,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortMatch {
+    pub side: PortSide,
+    pub connection: Connection,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortOwner {
+    pub pid: String,
+    pub image_name: Option<String>,
+    pub matches: Vec<PortMatch>,
+}
+
+impl PortOwner {
+    pub fn is_local(&self) -> bool {
+        self.matches.iter().any(|m| m.side == PortSide::Local)
+    }
+}
+
+/// Accepts `tcp`/`udp` in any case, as typed on the command line.
+pub fn parse_protocol(input: &str) -> Option<ConnectionType> {
+    match input.to_ascii_lowercase().as_str() {
+        "tcp" => Some(ConnectionType::Tcp),
+        "udp" => Some(ConnectionType::Udp),
+        _ => None,
+    }
+}
+
+/// Every process with a connection on `port`, local or foreign, optionally
+/// limited to one protocol. Processes that have the port bound locally come
+/// first.
+pub fn find_by_port(
+    connections: &[Connection],
+    processes: &[Process],
+    port: u16,
+    protocol: Option<ConnectionType>,
+) -> Vec<PortOwner> {
+    let names: HashMap<&str, &str> = processes
+        .iter()
+        .map(|process| (process.pid.as_str(), process.image_name.as_str()))
+        .collect();
+
+    let mut owners: Vec<PortOwner> = Vec::new();
+
+    for connection in connections {
+        if protocol.is_some_and(|protocol| protocol != connection.connection_type) {
+            continue;
+        }
+
+        let side = if connection.local_endpoint().and_then(|(_, p)| p) == Some(port) {
+            PortSide::Local
+        } else if connection.foreign_endpoint().and_then(|(_, p)| p) == Some(port) {
+            PortSide::Foreign
+        } else {
+            continue;
+        };
+
+        let index = match owners.iter().position(|o| o.pid == connection.pid) {
+            Some(index) => index,
+            None => {
+                owners.push(PortOwner {
+                    pid: connection.pid.clone(),
+                    image_name: names
+                        .get(connection.pid.as_str())
+                        .map(|name| name.to_string()),
+                    matches: Vec::new(),
+                });
+                owners.len() - 1
+            }
+        };
+        owners[index].matches.push(PortMatch {
+            side,
+            connection: connection.clone(),
+        });
+    }
+
+    // stable, so processes keep the order they were found in otherwise
+    owners.sort_by_key(|owner| !owner.is_local());
+    owners
+}
+
+/// Plain text rendering for the command line mode.
+pub fn format_port_owners(port: u16, owners: &[PortOwner]) -> String {
+    let mut output = String::new();
+
+    if owners.is_empty() {
+        writeln!(output, "nothing is using port {}", port).unwrap();
+        return output;
+    }
+
+    for owner in owners {
+        writeln!(
+            output,
+            "{} (pid {})",
+            owner.image_name.as_deref().unwrap_or("?"),
+            owner.pid
+        )
+        .unwrap();
+        for PortMatch { side, connection } in &owner.matches {
+            writeln!(
+                output,
+                "  {:<7} {:<3} {} -> {} {:?}",
+                match side {
+                    PortSide::Local => "local",
+                    PortSide::Foreign => "foreign",
+                },
+                match connection.connection_type {
+                    ConnectionType::Tcp => "tcp",
+                    ConnectionType::Udp => "udp",
+                },
+                connection.local_address,
+                connection.foreign_address,
+                connection.state
+            )
+            .unwrap();
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_connections;
+
+    const INPUT: &str = r#"
+        Active Connections
+
+        Proto  Local Address          Foreign Address        State           PID
+        TCP    0.0.0.0:5432           0.0.0.0:0              LISTENING       7292
+        TCP    127.0.0.1:5432         127.0.0.1:58110        ESTABLISHED     7304
+        TCP    127.0.0.1:58110        127.0.0.1:5432         ESTABLISHED     5236
+        TCP    192.168.100.14:57793   40.115.3.253:443       ESTABLISHED     5236
+        TCP    [::]:5432              [::]:0                 LISTENING       7292
+        UDP    0.0.0.0:5432           *:*                                    8812"#;
+
+    #[test]
+    fn test_find_by_port() {
+        let connections = parse_connections(INPUT);
+        let processes = vec![
+            Process::new("postgres.exe".to_string(), "7292".to_string()),
+            Process::new("Code.exe".to_string(), "5236".to_string()),
+        ];
+
+        let owners = find_by_port(&connections, &processes, 5432, Some(ConnectionType::Tcp));
+        let pids: Vec<&str> = owners.iter().map(|o| o.pid.as_str()).collect();
+        assert_eq!(pids, vec!["7292", "7304", "5236"]);
+
+        assert_eq!(owners[0].image_name.as_deref(), Some("postgres.exe"));
+        assert_eq!(owners[0].matches.len(), 2);
+        assert!(owners[0].is_local());
+        assert_eq!(owners[1].image_name, None);
+        assert!(!owners[2].is_local());
+        assert_eq!(owners[2].matches[0].side, PortSide::Foreign);
+
+        let owners = find_by_port(&connections, &processes, 5432, None);
+        assert_eq!(owners.len(), 4);
+
+        assert!(find_by_port(&connections, &processes, 1, None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_protocol() {
+        assert_eq!(parse_protocol("TCP"), Some(ConnectionType::Tcp));
+        assert_eq!(parse_protocol("udp"), Some(ConnectionType::Udp));
+        assert_eq!(parse_protocol("sctp"), None);
+    }
+
+    #[test]
+    fn test_format_port_owners() {
+        let connections = parse_connections(INPUT);
+        let owners = find_by_port(
+            &connections,
+            &[Process::new("postgres.exe".to_string(), "7292".to_string())],
+            5432,
+            Some(ConnectionType::Udp),
+        );
+
+        assert_eq!(
+            format_port_owners(5432, &owners),
+            "? (pid 8812)\n  local   udp 0.0.0.0:5432 -> *:* Close\n"
+        );
+        assert_eq!(format_port_owners(1, &[]), "nothing is using port 1\n");
+    }
+}
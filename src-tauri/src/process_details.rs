@@ -0,0 +1,286 @@
+//! Per-process details that are too expensive to collect for every process
+//! on every sample: executable path, command line, working directory, owner
+//! and, on Windows, the Authenticode signature of the executable.
+//!
+//! Every field is optional, the OS may refuse to tell us about processes
+//! owned by other users.
+
+use std::collections::HashMap;
+use std::io;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProcessDetails {
+    pub pid: String,
+    pub executable_path: Option<String>,
+    pub command_line: Option<String>,
+    pub working_directory: Option<String>,
+    pub owner: Option<String>,
+    /// Windows only.
+    pub signature: Option<Signature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Signature {
+    /// `Valid`, `NotSigned`, `HashMismatch`, ... as reported by
+    /// `Get-AuthenticodeSignature`.
+    pub status: String,
+    /// Subject of the signing certificate, e.g. `CN=Google LLC, O=Google LLC, ...`.
+    pub signer: Option<String>,
+}
+
+fn validate_pid(pid: &str) -> io::Result<()> {
+    if pid.is_empty() || !pid.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid pid: {:?}", pid),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub fn process_details(pid: &str) -> io::Result<ProcessDetails> {
+    use std::fs;
+    use std::path::Path;
+
+    validate_pid(pid)?;
+    let dir = Path::new("/proc").join(pid);
+    // fails with NotFound if the process is gone, everything below is optional
+    let status = fs::read_to_string(dir.join("status"))?;
+
+    let link = |name: &str| {
+        fs::read_link(dir.join(name))
+            .ok()
+            .map(|path| path.to_string_lossy().to_string())
+    };
+
+    let owner = parse_status_uid(&status).map(|uid| {
+        fs::read_to_string("/etc/passwd")
+            .ok()
+            .and_then(|passwd| parse_passwd(&passwd).remove(&uid))
+            .unwrap_or_else(|| uid.to_string())
+    });
+
+    Ok(ProcessDetails {
+        pid: pid.to_string(),
+        executable_path: link("exe"),
+        command_line: fs::read(dir.join("cmdline"))
+            .ok()
+            .and_then(|cmdline| parse_cmdline(&cmdline)),
+        working_directory: link("cwd"),
+        owner,
+        signature: None,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn process_details(pid: &str) -> io::Result<ProcessDetails> {
+    validate_pid(pid)?;
+
+    // Win32_Process has no working directory, reading it would mean poking
+    // around in the target's PEB
+    let script = format!(
+        "$p = Get-CimInstance Win32_Process -Filter 'ProcessId={pid}'
+if (-not $p) {{ exit 1 }}
+$o = Invoke-CimMethod -InputObject $p -MethodName GetOwner
+\"ExecutablePath=$($p.ExecutablePath)\"
+\"CommandLine=$($p.CommandLine)\"
+if ($o.User) {{ \"Owner=$($o.Domain)\\$($o.User)\" }}
+if ($p.ExecutablePath) {{
+  $s = Get-AuthenticodeSignature -LiteralPath $p.ExecutablePath
+  \"SignatureStatus=$($s.Status)\"
+  \"Signer=$($s.SignerCertificate.Subject)\"
+}}",
+        pid = pid
+    );
+
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no process with pid {}", pid),
+        ));
+    }
+
+    let mut details = parse_details_list(&String::from_utf8_lossy(&output.stdout));
+    details.pid = pid.to_string();
+    Ok(details)
+}
+
+/// Parses `/proc/<pid>/cmdline`: NUL separated arguments, empty for kernel
+/// threads.
+pub fn parse_cmdline(input: &[u8]) -> Option<String> {
+    let arguments: Vec<String> = input
+        .split(|b| *b == 0)
+        .filter(|argument| !argument.is_empty())
+        .map(|argument| String::from_utf8_lossy(argument).to_string())
+        .collect();
+
+    if arguments.is_empty() {
+        None
+    } else {
+        Some(arguments.join(" "))
+    }
+}
+
+/// The real UID from `/proc/<pid>/status`.
+pub fn parse_status_uid(input: &str) -> Option<u32> {
+    input
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))
+        .and_then(|uids| uids.split_whitespace().next())
+        .and_then(|uid| uid.parse().ok())
+}
+
+/// Maps UIDs to user names from `/etc/passwd`.
+pub fn parse_passwd(input: &str) -> HashMap<u32, String> {
+    let mut users = HashMap::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // name:password:uid:gid:gecos:home:shell
+        let fields: Vec<&str> = line.split(':').collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        if let Ok(uid) = fields[2].parse() {
+            users.entry(uid).or_insert_with(|| fields[0].to_string());
+        }
+    }
+
+    users
+}
+
+/// Parses the `Key=Value` lines printed by the PowerShell script in
+/// `process_details`. Empty values are treated as missing.
+pub fn parse_details_list(input: &str) -> ProcessDetails {
+    let mut details = ProcessDetails::default();
+    let mut signature_status = None;
+    let mut signer = None;
+
+    for line in input.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let value = Some(value.to_string());
+
+        match key.trim() {
+            "ExecutablePath" => details.executable_path = value,
+            "CommandLine" => details.command_line = value,
+            "Owner" => details.owner = value,
+            "SignatureStatus" => signature_status = value,
+            "Signer" => signer = value,
+            _ => {}
+        }
+    }
+
+    details.signature = signature_status.map(|status| Signature { status, signer });
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"/usr/lib/firefox/firefox\0-contentproc\0tab\0"),
+            Some("/usr/lib/firefox/firefox -contentproc tab".to_string())
+        );
+        assert_eq!(parse_cmdline(b""), None);
+    }
+
+    #[test]
+    fn test_parse_status_uid() {
+        let input = "Name:\tsshd\nUmask:\t0022\nState:\tS (sleeping)\nTgid:\t812\n\
+                     Uid:\t1000\t1000\t1000\t1000\nGid:\t1000\t1000\t1000\t1000\n";
+        assert_eq!(parse_status_uid(input), Some(1000));
+        assert_eq!(parse_status_uid("Name:\tsshd\n"), None);
+    }
+
+    #[test]
+    fn test_parse_passwd() {
+        let input = "root:x:0:0:root:/root:/bin/bash
+# comment
+systemd-resolve:x:101:103:systemd Resolver,,,:/run/systemd:/usr/sbin/nologin
+alice:x:1000:1000:Alice,,,:/home/alice:/bin/zsh
+broken
+";
+        let users = parse_passwd(input);
+        assert_eq!(users.len(), 3);
+        assert_eq!(users[&0], "root");
+        assert_eq!(users[&1000], "alice");
+    }
+
+    #[test]
+    fn test_parse_details_list() {
+        let input = "ExecutablePath=C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe\r
+CommandLine=\"C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe\" --type=renderer --lang=en-US\r
+Owner=DESKTOP-4H2\\alice\r
+SignatureStatus=Valid\r
+Signer=CN=Google LLC, O=Google LLC, L=Mountain View, S=California, C=US\r
+";
+        let details = parse_details_list(input);
+        assert_eq!(
+            details.executable_path.as_deref(),
+            Some("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe")
+        );
+        assert!(details
+            .command_line
+            .as_deref()
+            .unwrap()
+            .ends_with("--type=renderer --lang=en-US"));
+        assert_eq!(details.owner.as_deref(), Some("DESKTOP-4H2\\alice"));
+        assert_eq!(
+            details.signature,
+            Some(Signature {
+                status: "Valid".to_string(),
+                signer: Some(
+                    "CN=Google LLC, O=Google LLC, L=Mountain View, S=California, C=US".to_string()
+                ),
+            })
+        );
+
+        let details = parse_details_list("ExecutablePath=\r\nCommandLine=\r\n");
+        assert_eq!(details, ProcessDetails::default());
+    }
+
+    #[test]
+    fn test_invalid_pid() {
+        assert!(process_details("1; rm -rf /").is_err());
+        assert!(process_details("").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_details_self() {
+        let details = process_details(&std::process::id().to_string()).unwrap();
+        assert_eq!(
+            details.executable_path,
+            std::env::current_exe()
+                .ok()
+                .map(|path| path.to_string_lossy().to_string())
+        );
+        assert!(details.command_line.is_some());
+        assert!(details.owner.is_some());
+        assert_eq!(
+            details.working_directory,
+            std::env::current_dir()
+                .ok()
+                .map(|path| path.to_string_lossy().to_string())
+        );
+    }
+}
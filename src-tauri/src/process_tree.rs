@@ -0,0 +1,272 @@
+//! Parent/child view of the process list.
+//!
+//! Browsers, IDEs and the like run as dozens of helper processes. The tree
+//! rolls their connections and traffic up to the process that started them,
+//! so a whole application can be shown (and collapsed) as a single row.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::{Connection, Process};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessNode {
+    pub process: Process,
+    /// Connections owned by this process itself.
+    pub connections: usize,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// The same, summed over this process and all of its descendants.
+    pub total_connections: usize,
+    pub total_bytes_sent: u64,
+    pub total_bytes_received: u64,
+    pub children: Vec<ProcessNode>,
+}
+
+/// Sets `parent_pid` from a PID to parent PID map, for sources like
+/// `tasklist` that do not report it themselves.
+pub fn attach_parent_pids(processes: &mut [Process], parents: &HashMap<String, String>) {
+    for process in processes.iter_mut() {
+        if let Some(parent) = parents.get(&process.pid) {
+            process.parent_pid = Some(parent.clone());
+        }
+    }
+}
+
+/// Builds the process forest. Roots are processes whose parent is unknown or
+/// already gone. Traffic comes from `tcp_info`, so it is only counted where
+/// the backend provides it.
+pub fn build_process_tree(
+    processes: Vec<Process>,
+    connections: &HashMap<String, Vec<Connection>>,
+) -> Vec<ProcessNode> {
+    let mut by_pid: HashMap<String, Process> = HashMap::new();
+    let mut order = Vec::new();
+    for process in processes {
+        if !by_pid.contains_key(&process.pid) {
+            order.push(process.pid.clone());
+            by_pid.insert(process.pid.clone(), process);
+        }
+    }
+
+    let mut children: HashMap<String, Vec<String>> = HashMap::new();
+    let mut roots = Vec::new();
+    for pid in &order {
+        match by_pid[pid].parent_pid.as_ref() {
+            Some(parent) if parent != pid && by_pid.contains_key(parent) => {
+                children
+                    .entry(parent.clone())
+                    .or_default()
+                    .push(pid.clone());
+            }
+            _ => roots.push(pid.clone()),
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut tree: Vec<ProcessNode> = roots
+        .iter()
+        .map(|pid| build_node(pid, &by_pid, &children, connections, &mut visited))
+        .collect();
+
+    // PID reuse can produce parent cycles, which have no root; show them
+    // rather than dropping them
+    for pid in &order {
+        if !visited.contains(pid) {
+            tree.push(build_node(
+                pid,
+                &by_pid,
+                &children,
+                connections,
+                &mut visited,
+            ));
+        }
+    }
+
+    tree
+}
+
+fn build_node(
+    pid: &str,
+    by_pid: &HashMap<String, Process>,
+    children: &HashMap<String, Vec<String>>,
+    connections: &HashMap<String, Vec<Connection>>,
+    visited: &mut HashSet<String>,
+) -> ProcessNode {
+    visited.insert(pid.to_string());
+
+    let own = connections.get(pid).map(Vec::as_slice).unwrap_or_default();
+    let bytes_sent = own
+        .iter()
+        .filter_map(|connection| connection.tcp_info.as_ref())
+        .map(|info| info.bytes_acked)
+        .sum();
+    let bytes_received = own
+        .iter()
+        .filter_map(|connection| connection.tcp_info.as_ref())
+        .map(|info| info.bytes_received)
+        .sum();
+
+    let child_pids = children.get(pid).map(Vec::as_slice).unwrap_or_default();
+    let mut nodes = Vec::new();
+    for child in child_pids {
+        if !visited.contains(child) {
+            nodes.push(build_node(child, by_pid, children, connections, visited));
+        }
+    }
+
+    ProcessNode {
+        process: by_pid[pid].clone(),
+        connections: own.len(),
+        bytes_sent,
+        bytes_received,
+        total_connections: own.len() + nodes.iter().map(|n| n.total_connections).sum::<usize>(),
+        total_bytes_sent: bytes_sent + nodes.iter().map(|n| n.total_bytes_sent).sum::<u64>(),
+        total_bytes_received: bytes_received
+            + nodes.iter().map(|n| n.total_bytes_received).sum::<u64>(),
+        children: nodes,
+    }
+}
+
+/// PID to parent PID for every running process, from a Toolhelp snapshot.
+#[cfg(windows)]
+pub fn parent_pids() -> std::io::Result<HashMap<String, String>> {
+    use std::mem;
+    use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let mut parents = HashMap::new();
+
+    // SAFETY: `entry` is a properly sized PROCESSENTRY32W and the snapshot
+    // handle is closed before returning.
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut entry: PROCESSENTRY32W = mem::zeroed();
+        entry.dwSize = mem::size_of::<PROCESSENTRY32W>() as u32;
+
+        let mut more = Process32FirstW(snapshot, &mut entry) != 0;
+        while more {
+            parents.insert(
+                entry.th32ProcessID.to_string(),
+                entry.th32ParentProcessID.to_string(),
+            );
+            more = Process32NextW(snapshot, &mut entry) != 0;
+        }
+
+        CloseHandle(snapshot);
+    }
+
+    Ok(parents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_connection_line, parse_connections, TcpInfo};
+
+    fn process(pid: &str, parent_pid: Option<&str>, image_name: &str) -> Process {
+        let mut process = Process::new(image_name.to_string(), pid.to_string());
+        process.parent_pid = parent_pid.map(str::to_string);
+        process
+    }
+
+    #[test]
+    fn test_build_process_tree() {
+        let processes = vec![
+            process("4120", Some("1"), "explorer.exe"),
+            process("9444", Some("4120"), "chrome.exe"),
+            process("9460", Some("9444"), "chrome.exe"),
+            process("9512", Some("9444"), "chrome.exe"),
+            process("9600", Some("9512"), "chrome.exe"),
+            process("5236", Some("4120"), "Code.exe"),
+        ];
+        let mut connections = parse_connections(
+            r#"
+        Active Connections
+
+        Proto  Local Address          Foreign Address        State           PID
+        TCP    192.168.100.14:59060   140.82.121.4:443       ESTABLISHED     9444
+        TCP    192.168.100.14:59061   140.82.121.4:443       ESTABLISHED     9460
+        TCP    192.168.100.14:59062   140.82.121.4:443       ESTABLISHED     9600
+        TCP    192.168.100.14:59063   140.82.121.4:443       ESTABLISHED     9600
+        TCP    192.168.100.14:57793   40.115.3.253:443       ESTABLISHED     5236"#,
+        );
+        // (bytes acked, bytes received) of each row
+        let traffic = [(100, 1000), (10, 20), (1, 2), (1, 2), (5, 5)];
+        for (connection, (bytes_acked, bytes_received)) in connections.iter_mut().zip(traffic) {
+            connection.tcp_info = Some(TcpInfo {
+                bytes_acked,
+                bytes_received,
+                ..Default::default()
+            });
+        }
+        let connections = crate::group_connections_by_pid(connections);
+
+        let tree = build_process_tree(processes, &connections);
+        assert_eq!(tree.len(), 1);
+
+        let explorer = &tree[0];
+        assert_eq!(explorer.process.pid, "4120");
+        assert_eq!(explorer.connections, 0);
+        assert_eq!(explorer.total_connections, 5);
+
+        let chrome = &explorer.children[0];
+        assert_eq!(chrome.process.pid, "9444");
+        assert_eq!(chrome.children.len(), 2);
+        assert_eq!(chrome.connections, 1);
+        assert_eq!(chrome.total_connections, 4);
+        assert_eq!(chrome.bytes_sent, 100);
+        assert_eq!(chrome.total_bytes_sent, 112);
+        assert_eq!(chrome.total_bytes_received, 1024);
+    }
+
+    #[test]
+    fn test_build_process_tree_without_parents() {
+        let mut processes = vec![
+            process("0", Some("0"), "System Idle Process"),
+            process("4", None, "System"),
+            process("544", None, "smss.exe"),
+        ];
+        let tree = build_process_tree(processes.clone(), &HashMap::new());
+        assert_eq!(tree.len(), 3);
+
+        let parents = HashMap::from([
+            ("4".to_string(), "0".to_string()),
+            ("544".to_string(), "4".to_string()),
+        ]);
+        attach_parent_pids(&mut processes, &parents);
+        let tree = build_process_tree(processes, &HashMap::new());
+        assert_eq!(tree.len(), 1);
+        assert_eq!(
+            tree[0].children[0].children[0].process.image_name,
+            "smss.exe"
+        );
+    }
+
+    #[test]
+    fn test_build_process_tree_with_cycle() {
+        let processes = vec![
+            process("100", Some("200"), "a"),
+            process("200", Some("100"), "b"),
+            process("300", Some("999"), "orphan"),
+        ];
+        let connection =
+            parse_connection_line("TCP 192.168.100.14:59060 140.82.121.4:443 ESTABLISHED 200");
+        let connections = crate::group_connections_by_pid(connection.into_iter().collect());
+
+        let tree = build_process_tree(processes, &connections);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].process.pid, "300");
+        assert_eq!(tree[1].process.pid, "100");
+        assert_eq!(tree[1].children[0].process.pid, "200");
+        assert_eq!(tree[1].total_connections, 1);
+    }
+}
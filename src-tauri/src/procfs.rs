@@ -0,0 +1,228 @@
+//! Linux `/proc` backend.
+//!
+//! Reads `/proc/net/{tcp,tcp6,udp,udp6}` directly. It is slower than
+//! sock_diag and carries no `tcp_info`, but it only needs read access to
+//! `/proc`, so it is used as a fallback when netlink is not available.
+//! Other network namespaces (containers) are read through `/proc/<pid>/net`
+//! of one of their processes, see `containers` for the tagging.
+//! The process list comes from `/proc/<pid>/stat`, routes and the ARP cache
+//! from `/proc/net/{route,ipv6_route,arp}`, the extended counters from
+//! `/proc/net/netstat` and the `netstat -s -e` ones from
+//! `/proc/net/{snmp,snmp6,dev}`, all read by `stats_files`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::containers::{parse_ns_link, tag_connections, tag_processes, Placement};
+use crate::process_details::{parse_passwd, parse_status_uid};
+use crate::{
+    format_proc_net_files, parse_proc_net, parse_proc_net_arp, parse_proc_net_ipv6_route,
+    parse_proc_net_route, parse_proc_stat, Connection, ConnectionType, Neighbor, Process, Route,
+    PROC_NET_STATS_FILES,
+};
+
+const PROC_NET_FILES: [(&str, ConnectionType); 4] = [
+    ("tcp", ConnectionType::Tcp),
+    ("tcp6", ConnectionType::Tcp),
+    ("udp", ConnectionType::Udp),
+    ("udp6", ConnectionType::Udp),
+];
+
+/// Collects connections from `/proc/net`, with Send-Q/Recv-Q filled in, and
+/// from every other network namespace, tagged with namespace and container.
+pub fn connections() -> io::Result<Vec<Connection>> {
+    let owners = socket_owners();
+    let placements = placements();
+
+    let mut connections = read_proc_net(Path::new("/proc/net"), &owners)?;
+    let own = own_net_namespace();
+    for connection in &mut connections {
+        connection.net_namespace = own;
+    }
+    connections.extend(namespace_connections(&owners, &placements));
+    tag_connections(&mut connections, &placements);
+
+    Ok(connections)
+}
+
+/// The connections in the `tcp`, `udp`, ... files of `dir`. Missing files
+/// (e.g. IPv6 disabled) are skipped.
+fn read_proc_net(dir: &Path, owners: &HashMap<u32, String>) -> io::Result<Vec<Connection>> {
+    let mut connections = Vec::new();
+
+    for (name, connection_type) in PROC_NET_FILES {
+        match fs::read_to_string(dir.join(name)) {
+            Ok(input) => connections.extend(parse_proc_net(&input, connection_type, owners)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(connections)
+}
+
+pub fn own_net_namespace() -> Option<u64> {
+    let target = fs::read_link("/proc/self/ns/net").ok()?;
+    parse_ns_link(&target.to_string_lossy())
+}
+
+/// Network namespace and cgroup of every process, by PID. What we are not
+/// allowed to read about a process is left out.
+pub fn placements() -> HashMap<String, Placement> {
+    let mut placements = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return placements;
+    };
+
+    for entry in entries.flatten() {
+        let pid = entry.file_name().to_string_lossy().to_string();
+        if !pid.bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+
+        let ns_link = fs::read_link(entry.path().join("ns/net"))
+            .ok()
+            .map(|target| target.to_string_lossy().to_string());
+        let cgroup = fs::read_to_string(entry.path().join("cgroup")).ok();
+        placements.insert(pid, Placement::new(ns_link.as_deref(), cgroup.as_deref()));
+    }
+
+    placements
+}
+
+/// Connections of the network namespaces other than ours, each read through
+/// `/proc/<pid>/net` of one of its processes. Namespaces whose processes
+/// exit while we are reading are skipped.
+pub fn namespace_connections(
+    owners: &HashMap<u32, String>,
+    placements: &HashMap<String, Placement>,
+) -> Vec<Connection> {
+    let Some(own) = own_net_namespace() else {
+        return Vec::new();
+    };
+
+    let mut namespaces: BTreeMap<u64, &str> = BTreeMap::new();
+    for (pid, placement) in placements {
+        match placement.net_namespace {
+            Some(namespace) if namespace != own => {
+                namespaces.entry(namespace).or_insert(pid);
+            }
+            _ => (),
+        }
+    }
+
+    let mut connections = Vec::new();
+    for (namespace, pid) in namespaces {
+        let Ok(found) = read_proc_net(&Path::new("/proc").join(pid).join("net"), owners) else {
+            continue;
+        };
+        connections.extend(found.into_iter().map(|mut connection| {
+            connection.net_namespace = Some(namespace);
+            connection
+        }));
+    }
+
+    connections
+}
+
+/// Lists every process from `/proc/<pid>/stat`, with the owner from
+/// `/proc/<pid>/status`. Processes that exit while we are reading are skipped.
+pub fn processes() -> io::Result<Vec<Process>> {
+    let mut processes = Vec::new();
+    let users = fs::read_to_string("/etc/passwd")
+        .map(|passwd| parse_passwd(&passwd))
+        .unwrap_or_default();
+
+    for entry in fs::read_dir("/proc")?.flatten() {
+        let name = entry.file_name();
+        if !name.to_string_lossy().bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+
+        if let Some(mut process) = fs::read_to_string(entry.path().join("stat"))
+            .ok()
+            .and_then(|input| parse_proc_stat(&input))
+        {
+            process.user = fs::read_to_string(entry.path().join("status"))
+                .ok()
+                .and_then(|status| parse_status_uid(&status))
+                .map(|uid| users.get(&uid).cloned().unwrap_or_else(|| uid.to_string()));
+            processes.push(process);
+        }
+    }
+
+    tag_processes(&mut processes, &placements());
+    Ok(processes)
+}
+
+/// IPv4 and IPv6 routes. The IPv6 table is skipped when IPv6 is disabled.
+pub fn routes() -> io::Result<Vec<Route>> {
+    let mut routes = parse_proc_net_route(&fs::read_to_string("/proc/net/route")?);
+
+    match fs::read_to_string("/proc/net/ipv6_route") {
+        Ok(input) => routes.extend(parse_proc_net_ipv6_route(&input)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    Ok(routes)
+}
+
+/// The IPv4 neighbor cache. IPv6 neighbors are only available over netlink.
+pub fn arp() -> io::Result<Vec<Neighbor>> {
+    Ok(parse_proc_net_arp(&fs::read_to_string("/proc/net/arp")?))
+}
+
+/// The statistics files, joined for `parse_proc_net_stats` so that they can
+/// be recorded as one sample. `snmp6` is skipped when IPv6 is disabled.
+pub fn stats_files() -> io::Result<String> {
+    let mut files = Vec::new();
+    for name in PROC_NET_STATS_FILES {
+        match fs::read_to_string(Path::new("/proc/net").join(name)) {
+            Ok(input) => files.push((name, input)),
+            Err(err) if name == "snmp6" && err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(format_proc_net_files(&files))
+}
+
+/// Maps socket inodes to the PID holding them open, by walking the
+/// `socket:[inode]` links in `/proc/<pid>/fd`. Processes we are not allowed
+/// to inspect are skipped.
+pub fn socket_owners() -> HashMap<u32, String> {
+    let mut owners = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for entry in entries.flatten() {
+        let pid = entry.file_name().to_string_lossy().to_string();
+        if !pid.bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(target) = fs::read_link(fd.path()) else {
+                continue;
+            };
+            let target = target.to_string_lossy();
+            if let Some(inode) = target
+                .strip_prefix("socket:[")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|inode| inode.parse().ok())
+            {
+                owners.entry(inode).or_insert_with(|| pid.clone());
+            }
+        }
+    }
+
+    owners
+}
@@ -0,0 +1,298 @@
+//! Recording the raw outputs of the commands the app samples (`netstat`,
+//! `tasklist`, the `/proc/net` statistics files) and replaying them later,
+//! on any OS, through the same parsers. Lets us see what the app showed on somebody else's machine.
+//!
+//! A recording is a JSON lines file: a `RecordingHeader`, then one `Sample`
+//! per command run.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+const VERSION: u32 = 1;
+
+/// Which parser an output is meant for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// `netstat -s -e`, for `parse_stats`.
+    Stats,
+    /// The `/proc/net` statistics files of a Linux machine, joined by
+    /// `format_proc_net_files`, for `parse_proc_net_stats`.
+    ProcNet,
+    /// `netstat -ano`, `ss -tuanpeo` or `lsof -i -n -P`, for whichever
+    /// parser `import` detects.
+    Connections,
+    /// `tasklist /fo csv` or `lsof -i -n -P`, likewise.
+    Processes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub version: u32,
+    /// `std::env::consts::OS` of the recording machine.
+    pub os: String,
+    /// Unix timestamp of the start of the recording.
+    pub started: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sample {
+    pub source: Source,
+    /// Milliseconds since the start of the recording.
+    pub offset_ms: u64,
+    pub output: String,
+}
+
+/// Appends samples to a recording file. Samples can be recorded from
+/// several threads, each goes in whole on its own line.
+pub struct Recorder {
+    file: Mutex<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Starts a new recording at `path`, replacing any file already there.
+    pub fn create(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let header = RecordingHeader {
+            version: VERSION,
+            os: std::env::consts::OS.to_string(),
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        };
+        let mut file = File::create(&path)?;
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+
+        Ok(Recorder {
+            file: Mutex::new(file),
+            start: Instant::now(),
+        })
+    }
+
+    pub fn record(&self, source: Source, output: &str) -> io::Result<()> {
+        let sample = Sample {
+            source,
+            offset_ms: self.start.elapsed().as_millis() as u64,
+            output: output.to_string(),
+        };
+        let mut line = serde_json::to_string(&sample)?;
+        line.push('\n');
+        self.file.lock().unwrap().write_all(line.as_bytes())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub header: RecordingHeader,
+    /// Ordered by `offset_ms`.
+    pub samples: Vec<Sample>,
+}
+
+impl Recording {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let input =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        parse_recording(&input).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Length of the recording, up to its last sample.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.samples.last().map_or(0, |sample| sample.offset_ms))
+    }
+
+    /// The output the app had for `source` at `offset` into the recording:
+    /// the last sample taken by then, or the first one if there is none yet.
+    pub fn output_at(&self, source: Source, offset: Duration) -> Option<&str> {
+        let offset = offset.as_millis() as u64;
+        let mut samples = self.samples.iter().filter(|sample| sample.source == source);
+        let first = samples.next()?;
+        let sample = samples
+            .take_while(|sample| sample.offset_ms <= offset)
+            .last()
+            .unwrap_or(first);
+        Some(&sample.output)
+    }
+}
+
+pub fn parse_recording(input: &str) -> Result<Recording, String> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().ok_or("empty recording")?;
+    let header: RecordingHeader =
+        serde_json::from_str(header).map_err(|err| format!("line 1: {}", err))?;
+    if header.version != VERSION {
+        return Err(format!("unsupported recording version {}", header.version));
+    }
+
+    let mut samples = lines
+        .map(|(index, line)| {
+            serde_json::from_str::<Sample>(line)
+                .map_err(|err| format!("line {}: {}", index + 1, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    samples.sort_by_key(|sample| sample.offset_ms);
+
+    Ok(Recording { header, samples })
+}
+
+/// Plays a recording back in real time, or `speed` times faster. Once past
+/// the end it keeps showing the last samples.
+pub struct Replay {
+    recording: Recording,
+    start: Instant,
+    speed: f64,
+}
+
+impl Replay {
+    pub fn new(recording: Recording, speed: f64) -> Self {
+        Replay {
+            recording,
+            start: Instant::now(),
+            speed,
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// How far into the recording the replay is.
+    pub fn position(&self) -> Duration {
+        self.start.elapsed().mul_f64(self.speed)
+    }
+
+    pub fn output(&self, source: Source) -> Option<&str> {
+        self.recording.output_at(source, self.position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_replay() {
+        let path = std::env::temp_dir().join(format!(
+            "net-process-recording-{}.jsonl",
+            std::process::id()
+        ));
+        let recorder = Recorder::create(&path).unwrap();
+        recorder.record(Source::Stats, "IPv4 Statistics\n").unwrap();
+        recorder
+            .record(Source::Processes, "\"Image Name\",\"PID\"\n")
+            .unwrap();
+
+        let recording = Recording::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(recording.header.version, VERSION);
+        assert_eq!(recording.header.os, std::env::consts::OS);
+        assert_eq!(recording.samples.len(), 2);
+        assert_eq!(recording.samples[0].source, Source::Stats);
+        assert_eq!(recording.samples[0].output, "IPv4 Statistics\n");
+
+        let replay = Replay::new(recording, 1.0);
+        assert_eq!(replay.output(Source::Stats), Some("IPv4 Statistics\n"));
+        assert_eq!(replay.output(Source::Connections), None);
+    }
+
+    #[test]
+    fn test_record_from_threads() {
+        let path = std::env::temp_dir().join(format!(
+            "net-process-recording-threads-{}.jsonl",
+            std::process::id()
+        ));
+        let recorder = Recorder::create(&path).unwrap();
+        let output = "Proto  Local Address  Foreign Address  State  PID\n".repeat(200);
+        std::thread::scope(|scope| {
+            for source in [Source::Stats, Source::Connections, Source::Processes] {
+                let (recorder, output) = (&recorder, &output);
+                scope.spawn(move || {
+                    for _ in 0..50 {
+                        recorder.record(source, output).unwrap();
+                    }
+                });
+            }
+        });
+
+        let recording = Recording::open(&path);
+        fs::remove_file(&path).unwrap();
+        let recording = recording.unwrap();
+        assert_eq!(recording.samples.len(), 150);
+        assert!(recording
+            .samples
+            .iter()
+            .all(|sample| sample.output == output));
+    }
+
+    #[test]
+    fn test_output_at() {
+        let input = r#"{"version":1,"os":"windows","started":1700000000}
+{"source":"connections","offset_ms":0,"output":"a"}
+{"source":"stats","offset_ms":500,"output":"s"}
+{"source":"connections","offset_ms":2500,"output":"c"}
+{"source":"connections","offset_ms":1000,"output":"b"}
+"#;
+        let recording = parse_recording(input).unwrap();
+        assert_eq!(recording.header.os, "windows");
+        assert_eq!(recording.duration(), Duration::from_millis(2500));
+
+        let at = |ms| Duration::from_millis(ms);
+        assert_eq!(recording.output_at(Source::Connections, at(0)), Some("a"));
+        assert_eq!(recording.output_at(Source::Connections, at(999)), Some("a"));
+        assert_eq!(
+            recording.output_at(Source::Connections, at(1000)),
+            Some("b")
+        );
+        assert_eq!(
+            recording.output_at(Source::Connections, at(60000)),
+            Some("c")
+        );
+        // before the first stats sample
+        assert_eq!(recording.output_at(Source::Stats, at(0)), Some("s"));
+        assert_eq!(recording.output_at(Source::Processes, at(0)), None);
+    }
+
+    #[test]
+    fn test_replay_proc_net() {
+        let input = r#"{"version":1,"os":"linux","started":1700000000}
+{"source":"proc_net","offset_ms":0,"output":"==> /proc/net/snmp <==\nTcp: RetransSegs\nTcp: 24199\n"}
+"#;
+        let recording = parse_recording(input).unwrap();
+        assert_eq!(recording.output_at(Source::Stats, Duration::ZERO), None);
+
+        let output = recording
+            .output_at(Source::ProcNet, Duration::ZERO)
+            .unwrap();
+        let (snapshot, _) = crate::parse_proc_net_stats(output);
+        assert_eq!(
+            snapshot.get("tcp_ipv4", "segments_retransmitted"),
+            Some(crate::StatValue::Count(24199))
+        );
+    }
+
+    #[test]
+    fn test_parse_recording_errors() {
+        assert!(parse_recording("").is_err());
+        assert!(parse_recording(r#"{"version":2,"os":"linux","started":0}"#)
+            .unwrap_err()
+            .contains("version 2"));
+        let err = parse_recording(
+            "{\"version\":1,\"os\":\"linux\",\"started\":0}\n{\"source\":\"nope\"}\n",
+        )
+        .unwrap_err();
+        assert!(err.starts_with("line 2"), "{}", err);
+    }
+}
@@ -0,0 +1,376 @@
+//! Alerting rules, evaluated against connections that appeared since the
+//! previous snapshot.
+//!
+//! Rules live in a TOML file, one `[[rule]]` table each. Every condition a
+//! rule sets must hold for it to match:
+//!
+//! ```toml
+//! [[rule]]
+//! name = "Browser talking to the internet"
+//! process = "chrome.exe"
+//! external = true
+//!
+//! [[rule]]
+//! name = "New listener"
+//! state = "Listen"
+//!
+//! [[rule]]
+//! name = "Outgoing mail"
+//! protocol = "Tcp"
+//! foreign_port = 25
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AddressClass, Connection, ConnectionState, ConnectionType};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    /// Image name of the owning process, compared case-insensitively.
+    pub process: Option<String>,
+    pub protocol: Option<ConnectionType>,
+    pub state: Option<ConnectionState>,
+    /// Matches either end of the connection.
+    pub port: Option<u16>,
+    pub local_port: Option<u16>,
+    pub foreign_port: Option<u16>,
+    pub foreign_class: Option<AddressClass>,
+    /// `true` for public foreign addresses, `false` for everything else.
+    pub external: Option<bool>,
+    pub service: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RuleFile {
+    #[serde(default)]
+    rule: Vec<Rule>,
+}
+
+impl Rule {
+    fn has_conditions(&self) -> bool {
+        self.process.is_some()
+            || self.protocol.is_some()
+            || self.state.is_some()
+            || self.port.is_some()
+            || self.local_port.is_some()
+            || self.foreign_port.is_some()
+            || self.foreign_class.is_some()
+            || self.external.is_some()
+            || self.service.is_some()
+    }
+
+    pub fn matches(&self, connection: &Connection, image_name: Option<&str>) -> bool {
+        let local_port = connection.local_endpoint().and_then(|(_, port)| port);
+        let foreign_port = connection.foreign_endpoint().and_then(|(_, port)| port);
+
+        self.process
+            .as_ref()
+            .is_none_or(|process| image_name.is_some_and(|name| name.eq_ignore_ascii_case(process)))
+            && self
+                .protocol
+                .is_none_or(|p| p == connection.connection_type)
+            && self.state.is_none_or(|s| s == connection.state)
+            && self
+                .port
+                .is_none_or(|p| local_port == Some(p) || foreign_port == Some(p))
+            && self.local_port.is_none_or(|p| local_port == Some(p))
+            && self.foreign_port.is_none_or(|p| foreign_port == Some(p))
+            && self
+                .foreign_class
+                .is_none_or(|class| connection.foreign_class == Some(class))
+            && self.external.is_none_or(|external| {
+                connection
+                    .foreign_class
+                    .is_some_and(|class| class.is_external())
+                    == external
+            })
+            && self
+                .service
+                .as_ref()
+                .is_none_or(|service| connection.service.as_ref() == Some(service))
+    }
+}
+
+/// Parses a rules file. Rules without any condition are rejected, they would
+/// fire on every new connection.
+pub fn parse_rules(input: &str) -> Result<Vec<Rule>, String> {
+    let file: RuleFile = toml::from_str(input).map_err(|err| err.to_string())?;
+
+    if let Some(rule) = file.rule.iter().find(|rule| !rule.has_conditions()) {
+        return Err(format!("rule {:?} has no conditions", rule.name));
+    }
+    Ok(file.rule)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub rule: String,
+    pub timestamp: u64,
+    pub image_name: Option<String>,
+    pub connection: Connection,
+}
+
+impl Alert {
+    /// One line summary, used as the notification body.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} (pid {}): {:?} {} -> {} {:?}",
+            self.image_name.as_deref().unwrap_or("?"),
+            self.connection.pid,
+            self.connection.connection_type,
+            self.connection.local_address,
+            self.connection.foreign_address,
+            self.connection.state
+        )
+    }
+}
+
+// a connection is "new" when this tuple was not in the previous snapshot, so a
+// socket moving from SYN_SENT to ESTABLISHED (or to LISTEN) is seen again
+type ConnectionKey = (ConnectionType, String, String, ConnectionState, String);
+
+fn connection_key(connection: &Connection) -> ConnectionKey {
+    (
+        connection.connection_type,
+        connection.local_address.clone(),
+        connection.foreign_address.clone(),
+        connection.state,
+        connection.pid.clone(),
+    )
+}
+
+/// Keeps the previous snapshot around so only new connections are evaluated.
+/// The first snapshot only primes it: whatever was running before the app
+/// started does not raise alerts.
+#[derive(Debug, Default)]
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+    previous: Option<HashSet<ConnectionKey>>,
+}
+
+impl RuleEngine {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        RuleEngine {
+            rules,
+            previous: None,
+        }
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Replaces the rules, keeping the snapshot.
+    pub fn set_rules(&mut self, rules: Vec<Rule>) {
+        self.rules = rules;
+    }
+
+    /// Diffs `connections` against the previous snapshot and returns one alert
+    /// per (new connection, matching rule). `image_names` maps PIDs to
+    /// process names for `process` conditions.
+    pub fn evaluate(
+        &mut self,
+        connections: &[Connection],
+        image_names: &HashMap<String, String>,
+    ) -> Vec<Alert> {
+        let current: HashSet<ConnectionKey> = connections.iter().map(connection_key).collect();
+        let Some(previous) = self.previous.replace(current) else {
+            return Vec::new();
+        };
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        let mut alerts = Vec::new();
+        for connection in connections {
+            if previous.contains(&connection_key(connection)) {
+                continue;
+            }
+
+            let image_name = image_names.get(&connection.pid).map(String::as_str);
+            for rule in self
+                .rules
+                .iter()
+                .filter(|r| r.matches(connection, image_name))
+            {
+                alerts.push(Alert {
+                    rule: rule.name.clone(),
+                    timestamp,
+                    image_name: image_name.map(str::to_string),
+                    connection: connection.clone(),
+                });
+            }
+        }
+        alerts
+    }
+}
+
+/// Appends alerts to `path` as JSON lines.
+pub fn log_alerts<T: Serialize>(path: &Path, alerts: &[T]) -> io::Result<()> {
+    if alerts.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for alert in alerts {
+        writeln!(file, "{}", serde_json::to_string(alert)?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+[[rule]]
+name = "Browser to the internet"
+process = "chrome.exe"
+external = true
+
+[[rule]]
+name = "New listener"
+state = "Listen"
+
+[[rule]]
+name = "SMTP"
+port = 25
+"#;
+
+    fn connection(local: &str, foreign: &str, state: ConnectionState, pid: &str) -> Connection {
+        Connection::new(
+            ConnectionType::Tcp,
+            local.to_string(),
+            foreign.to_string(),
+            state,
+            pid.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = parse_rules(RULES).unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].process.as_deref(), Some("chrome.exe"));
+        assert_eq!(rules[0].external, Some(true));
+        assert_eq!(rules[1].state, Some(ConnectionState::Listen));
+        assert_eq!(rules[2].port, Some(25));
+
+        assert!(parse_rules("").unwrap().is_empty());
+        assert!(parse_rules("[[rule]]\nname = \"everything\"\n").is_err());
+        assert!(parse_rules("[[rule]]\nname = \"typo\"\nprot = 25\n").is_err());
+        assert!(parse_rules("[[rule]]\nname = \"bad\"\nstate = \"Open\"\n").is_err());
+    }
+
+    #[test]
+    fn test_rule_matches() {
+        let rules = parse_rules(RULES).unwrap();
+        let public = connection(
+            "192.168.100.14:59060",
+            "140.82.121.4:443",
+            ConnectionState::Established,
+            "9444",
+        );
+        let private = connection(
+            "192.168.100.14:59061",
+            "192.168.100.1:443",
+            ConnectionState::Established,
+            "9444",
+        );
+        let smtp = connection(
+            "192.168.100.14:59062",
+            "40.97.120.226:25",
+            ConnectionState::SynSent,
+            "6010",
+        );
+
+        assert!(rules[0].matches(&public, Some("Chrome.exe")));
+        assert!(!rules[0].matches(&public, Some("firefox.exe")));
+        assert!(!rules[0].matches(&public, None));
+        assert!(!rules[0].matches(&private, Some("chrome.exe")));
+        assert!(rules[2].matches(&smtp, None));
+        assert!(!rules[2].matches(&public, None));
+    }
+
+    #[test]
+    fn test_engine_only_alerts_on_new_connections() {
+        let mut engine = RuleEngine::new(parse_rules(RULES).unwrap());
+        let names = HashMap::from([("9444".to_string(), "chrome.exe".to_string())]);
+
+        let mut snapshot = vec![
+            connection("0.0.0.0:135", "0.0.0.0:0", ConnectionState::Listen, "1192"),
+            connection(
+                "192.168.100.14:59060",
+                "140.82.121.4:443",
+                ConnectionState::Established,
+                "9444",
+            ),
+        ];
+        assert!(engine.evaluate(&snapshot, &names).is_empty());
+        assert!(engine.evaluate(&snapshot, &names).is_empty());
+
+        snapshot.push(connection(
+            "0.0.0.0:5432",
+            "0.0.0.0:0",
+            ConnectionState::Listen,
+            "7292",
+        ));
+        snapshot.push(connection(
+            "192.168.100.14:59070",
+            "34.36.232.77:443",
+            ConnectionState::Established,
+            "9444",
+        ));
+
+        let alerts = engine.evaluate(&snapshot, &names);
+        let rules: Vec<&str> = alerts.iter().map(|a| a.rule.as_str()).collect();
+        assert_eq!(rules, vec!["New listener", "Browser to the internet"]);
+        assert_eq!(alerts[1].image_name.as_deref(), Some("chrome.exe"));
+        assert!(alerts[1]
+            .summary()
+            .starts_with("chrome.exe (pid 9444): Tcp"));
+
+        assert!(engine.evaluate(&snapshot, &names).is_empty());
+    }
+
+    #[test]
+    fn test_log_alerts() {
+        let path =
+            std::env::temp_dir().join(format!("net-process-alerts-{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let alert = Alert {
+            rule: "SMTP".to_string(),
+            timestamp: 1700000000,
+            image_name: None,
+            connection: connection(
+                "192.168.100.14:59062",
+                "40.97.120.226:25",
+                ConnectionState::SynSent,
+                "6010",
+            ),
+        };
+        log_alerts(&path, &[alert.clone(), alert]).unwrap();
+        log_alerts::<Alert>(&path, &[]).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents.lines().count(), 2);
+        let line: serde_json::Value =
+            serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        assert_eq!(line["rule"], "SMTP");
+        assert_eq!(line["connection"]["pid"], "6010");
+    }
+}
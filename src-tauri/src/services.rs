@@ -0,0 +1,291 @@
+//! Port to service name lookup.
+//!
+//! Starts from a small bundled subset of the IANA registry and, when
+//! available, overlays the system's own `services` file (`/etc/services`, or
+//! `%SystemRoot%\System32\drivers\etc\services` on Windows), which share the
+//! same format.
+
+use std::collections::HashMap;
+use std::fs;
+use std::ops::RangeInclusive;
+
+use crate::{Connection, ConnectionType};
+
+/// IANA dynamic/private port range, used when the OS does not tell us better.
+pub const IANA_EPHEMERAL_PORTS: RangeInclusive<u16> = 49152..=65535;
+
+const BUNDLED_SERVICES: &str = "
+ftp-data        20/tcp
+ftp             21/tcp
+ssh             22/tcp
+telnet          23/tcp
+smtp            25/tcp
+domain          53/tcp
+domain          53/udp
+bootps          67/udp
+bootpc          68/udp
+tftp            69/udp
+http            80/tcp
+http            80/udp
+kerberos        88/tcp
+kerberos        88/udp
+pop3            110/tcp
+sunrpc          111/tcp
+sunrpc          111/udp
+ntp             123/udp
+epmap           135/tcp
+netbios-ns      137/udp
+netbios-dgm     138/udp
+netbios-ssn     139/tcp
+imap            143/tcp
+snmp            161/udp
+snmp-trap       162/udp
+ldap            389/tcp
+https           443/tcp
+https           443/udp
+microsoft-ds    445/tcp
+isakmp          500/udp
+submissions     465/tcp
+syslog          514/udp
+submission      587/tcp
+ipp             631/tcp
+ldaps           636/tcp
+imaps           993/tcp
+pop3s           995/tcp
+socks           1080/tcp
+openvpn         1194/udp
+ms-sql-s        1433/tcp
+ms-sql-m        1434/udp
+l2tp            1701/udp
+pptp            1723/tcp
+ssdp            1900/udp
+mqtt            1883/tcp
+nfs             2049/tcp
+nfs             2049/udp
+docker          2375/tcp
+docker-s        2376/tcp
+mysql           3306/tcp
+ms-wbt-server   3389/tcp
+ms-wbt-server   3389/udp
+ws-discovery    3702/udp
+ipsec-nat-t     4500/udp
+sip             5060/udp
+sip             5060/tcp
+mdns            5353/udp
+llmnr           5355/udp
+llmnr           5355/tcp
+postgresql      5432/tcp
+amqp            5672/tcp
+wsd             5357/tcp
+x11             6000/tcp
+redis           6379/tcp
+irc             6667/tcp
+http-alt        8080/tcp
+https-alt       8443/tcp
+git             9418/tcp
+memcache        11211/tcp
+mongodb         27017/tcp
+";
+
+#[derive(Debug, Clone)]
+pub struct ServiceTable {
+    services: HashMap<(u16, ConnectionType), String>,
+    ephemeral: RangeInclusive<u16>,
+}
+
+impl ServiceTable {
+    /// The bundled table only, with the IANA ephemeral range.
+    pub fn bundled() -> Self {
+        ServiceTable::parse(BUNDLED_SERVICES)
+    }
+
+    /// Parses a `services` file. Only tcp and udp entries are kept.
+    pub fn parse(input: &str) -> Self {
+        ServiceTable {
+            services: parse_services(input),
+            ephemeral: IANA_EPHEMERAL_PORTS,
+        }
+    }
+
+    /// The bundled table overlaid with the system's `services` file and, on
+    /// Linux, the configured local port range.
+    pub fn system() -> Self {
+        let mut table = ServiceTable::bundled();
+
+        if let Ok(input) = fs::read_to_string(system_services_path()) {
+            table.merge(parse_services(&input));
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(range) = fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range")
+            .ok()
+            .and_then(|input| parse_port_range(&input))
+        {
+            table.ephemeral = range;
+        }
+
+        table
+    }
+
+    pub fn with_ephemeral_range(mut self, ephemeral: RangeInclusive<u16>) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    pub fn lookup(&self, port: u16, protocol: ConnectionType) -> Option<&str> {
+        self.services.get(&(port, protocol)).map(String::as_str)
+    }
+
+    pub fn is_ephemeral(&self, port: u16) -> bool {
+        self.ephemeral.contains(&port)
+    }
+
+    /// Sets `service` to the name of whichever end of the connection uses a
+    /// well-known port (the local one wins, so listeners are named after
+    /// what they serve), and flags connections with an ephemeral local port.
+    pub fn annotate(&self, connections: &mut [Connection]) {
+        for connection in connections.iter_mut() {
+            let local_port = connection.local_endpoint().and_then(|(_, port)| port);
+            let foreign_port = connection.foreign_endpoint().and_then(|(_, port)| port);
+
+            connection.ephemeral_local_port =
+                local_port.is_some_and(|port| self.is_ephemeral(port));
+            connection.service = [local_port, foreign_port]
+                .into_iter()
+                .flatten()
+                .filter(|port| !self.is_ephemeral(*port))
+                .find_map(|port| self.lookup(port, connection.connection_type))
+                .map(str::to_string);
+        }
+    }
+
+    fn merge(&mut self, services: HashMap<(u16, ConnectionType), String>) {
+        self.services.extend(services);
+    }
+}
+
+fn system_services_path() -> String {
+    if cfg!(windows) {
+        let root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+        format!("{}\\System32\\drivers\\etc\\services", root)
+    } else {
+        "/etc/services".to_string()
+    }
+}
+
+// name  port/protocol  [aliases...]  [# comment]
+fn parse_services(input: &str) -> HashMap<(u16, ConnectionType), String> {
+    let mut services = HashMap::new();
+
+    let lines = input
+        .lines()
+        .map(|line| line.split_once('#').map_or(line, |(line, _)| line).trim())
+        .filter(|line| !line.is_empty());
+
+    for line in lines {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        if tokens.len() < 2 {
+            continue;
+        }
+
+        let Some((port, protocol)) = tokens[1].split_once('/') else {
+            continue;
+        };
+        let Ok(port) = port.parse() else {
+            continue;
+        };
+        let protocol = match protocol {
+            "tcp" => ConnectionType::Tcp,
+            "udp" => ConnectionType::Udp,
+            _ => continue,
+        };
+
+        // the first entry for a port is the canonical one
+        services
+            .entry((port, protocol))
+            .or_insert_with(|| tokens[0].to_string());
+    }
+
+    services
+}
+
+#[cfg(target_os = "linux")]
+fn parse_port_range(input: &str) -> Option<RangeInclusive<u16>> {
+    let mut tokens = input.split_whitespace();
+    let start = tokens.next()?.parse().ok()?;
+    let end = tokens.next()?.parse().ok()?;
+    Some(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_connections;
+
+    #[test]
+    fn test_parse_services() {
+        let input = r#"
+# Network services, Internet style
+tcpmux          1/tcp                           # TCP port service multiplexer
+echo            7/tcp
+echo            7/udp
+ssh             22/tcp                          # SSH Remote Login Protocol
+domain          53/tcp
+domain          53/udp
+http            80/tcp          www             # WorldWideWeb HTTP
+sctp-only       9999/sctp
+postgresql      5432/tcp        postgres        # PostgreSQL Database
+"#;
+
+        let table = ServiceTable::parse(input);
+
+        assert_eq!(table.lookup(22, ConnectionType::Tcp), Some("ssh"));
+        assert_eq!(table.lookup(22, ConnectionType::Udp), None);
+        assert_eq!(table.lookup(53, ConnectionType::Udp), Some("domain"));
+        assert_eq!(table.lookup(80, ConnectionType::Tcp), Some("http"));
+        assert_eq!(table.lookup(5432, ConnectionType::Tcp), Some("postgresql"));
+        assert_eq!(table.lookup(9999, ConnectionType::Tcp), None);
+    }
+
+    #[test]
+    fn test_bundled() {
+        let table = ServiceTable::bundled();
+
+        assert_eq!(table.lookup(443, ConnectionType::Tcp), Some("https"));
+        assert_eq!(table.lookup(5353, ConnectionType::Udp), Some("mdns"));
+        assert!(table.is_ephemeral(57793));
+        assert!(!table.is_ephemeral(8080));
+    }
+
+    #[test]
+    fn test_annotate() {
+        let table = ServiceTable::bundled();
+        let mut connections = parse_connections(
+            r#"
+        Active Connections
+
+        Proto  Local Address          Foreign Address        State           PID
+        TCP    192.168.100.14:57793   40.115.3.253:443       ESTABLISHED     5236
+        TCP    0.0.0.0:5432           0.0.0.0:0              LISTENING       7292
+        UDP    [::]:5353              *:*                                    3604
+        TCP    127.0.0.1:57784        127.0.0.1:65001        ESTABLISHED     5124"#,
+        );
+
+        table.annotate(&mut connections);
+
+        assert_eq!(connections[0].service.as_deref(), Some("https"));
+        assert!(connections[0].ephemeral_local_port);
+        assert_eq!(connections[1].service.as_deref(), Some("postgresql"));
+        assert!(!connections[1].ephemeral_local_port);
+        assert_eq!(connections[2].service.as_deref(), Some("mdns"));
+        assert_eq!(connections[3].service, None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_port_range() {
+        assert_eq!(parse_port_range("32768\t60999\n"), Some(32768..=60999));
+        assert_eq!(parse_port_range("32768"), None);
+    }
+}
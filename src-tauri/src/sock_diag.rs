@@ -0,0 +1,511 @@
+//! Linux `NETLINK_SOCK_DIAG` backend.
+//!
+//! Asks the kernel directly for every TCP/UDP socket (the same interface `ss`
+//! uses) instead of parsing `netstat` output. For TCP sockets the reply also
+//! carries `struct tcp_info`, which is where RTT, cwnd and retransmit counts
+//! come from.
+
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::containers::tag_connections;
+use crate::{procfs, Connection, ConnectionState, ConnectionType, TcpInfo};
+
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const SOCK_DESTROY: u16 = 21;
+const INET_DIAG_INFO: u16 = 2;
+
+const NLMSG_HDR_LEN: usize = 16;
+const INET_DIAG_SOCKID_LEN: usize = 48;
+const INET_DIAG_REQ_V2_LEN: usize = 8 + INET_DIAG_SOCKID_LEN;
+const INET_DIAG_MSG_LEN: usize = 4 + INET_DIAG_SOCKID_LEN + 20;
+
+/// All `TCP_*` states, used as the `idiag_states` filter.
+const ALL_STATES: u32 = 0xfff;
+/// `INET_DIAG_NOCOOKIE`, match the socket by address only.
+const NO_COOKIE: u32 = !0;
+
+/// A socket as reported by sock_diag, before it is joined with its owner.
+#[derive(Debug, Clone)]
+pub struct DiagSocket {
+    pub connection_type: ConnectionType,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub state: u8,
+    pub inode: u32,
+    pub send_queue: u32,
+    pub recv_queue: u32,
+    pub info: TcpInfo,
+}
+
+/// Dumps every TCP socket, including its `tcp_info`.
+pub fn tcp_sockets() -> io::Result<Vec<DiagSocket>> {
+    let mut sockets = dump(libc::AF_INET as u8, libc::IPPROTO_TCP as u8)?;
+    sockets.extend(dump(libc::AF_INET6 as u8, libc::IPPROTO_TCP as u8)?);
+    Ok(sockets)
+}
+
+/// Dumps every UDP socket.
+pub fn udp_sockets() -> io::Result<Vec<DiagSocket>> {
+    let mut sockets = dump(libc::AF_INET as u8, libc::IPPROTO_UDP as u8)?;
+    sockets.extend(dump(libc::AF_INET6 as u8, libc::IPPROTO_UDP as u8)?);
+    Ok(sockets)
+}
+
+/// Collects TCP and UDP connections with `tcp_info` filled in and the owning
+/// PID resolved through `/proc/<pid>/fd`.
+pub fn connections() -> io::Result<Vec<Connection>> {
+    let owners = procfs::socket_owners();
+    let mut sockets = tcp_sockets()?;
+    sockets.extend(udp_sockets()?);
+
+    let own = procfs::own_net_namespace();
+    let mut connections: Vec<Connection> = sockets
+        .into_iter()
+        .filter_map(|socket| {
+            let state = ConnectionState::from_tcp_state(socket.state)?;
+            let pid = owners
+                .get(&socket.inode)
+                .cloned()
+                .unwrap_or_else(|| "0".to_string());
+
+            let mut connection = Connection::new(
+                socket.connection_type,
+                socket.local.to_string(),
+                socket.remote.to_string(),
+                state,
+                pid,
+            );
+            connection.send_queue = Some(socket.send_queue as u64);
+            connection.recv_queue = Some(socket.recv_queue as u64);
+            connection.uid = Some(socket.info.uid);
+            connection.inode = Some(socket.inode as u64);
+            connection.net_namespace = own;
+            if socket.connection_type == ConnectionType::Tcp {
+                connection.tcp_info = Some(socket.info);
+            }
+            Some(connection)
+        })
+        .collect();
+
+    // sock_diag only sees our own namespace
+    let placements = procfs::placements();
+    connections.extend(procfs::namespace_connections(&owners, &placements));
+    tag_connections(&mut connections, &placements);
+
+    Ok(connections)
+}
+
+/// Fills in `tcp_info` (and the queue sizes, if the backend had none) on
+/// connections gathered by another backend (e.g. `netstat`), matching them on
+/// their local and foreign addresses.
+pub fn attach_tcp_info(connections: &mut [Connection], sockets: &[DiagSocket]) {
+    let by_address: HashMap<(String, String), &DiagSocket> = sockets
+        .iter()
+        .map(|socket| {
+            (
+                (socket.local.to_string(), socket.remote.to_string()),
+                socket,
+            )
+        })
+        .collect();
+
+    for connection in connections
+        .iter_mut()
+        .filter(|connection| connection.connection_type == ConnectionType::Tcp)
+    {
+        let key = (
+            connection.local_address.clone(),
+            connection.foreign_address.clone(),
+        );
+        if let Some(socket) = by_address.get(&key) {
+            connection.tcp_info = Some(socket.info.clone());
+            connection
+                .send_queue
+                .get_or_insert(socket.send_queue as u64);
+            connection
+                .recv_queue
+                .get_or_insert(socket.recv_queue as u64);
+        }
+    }
+}
+
+fn dump(family: u8, protocol: u8) -> io::Result<Vec<DiagSocket>> {
+    let socket = open_netlink()?;
+
+    let mut request = netlink_header(
+        NLMSG_HDR_LEN + INET_DIAG_REQ_V2_LEN,
+        SOCK_DIAG_BY_FAMILY,
+        (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16,
+    );
+    request.push(family);
+    request.push(protocol);
+    request.push(1 << (INET_DIAG_INFO - 1));
+    request.push(0);
+    request.extend_from_slice(&ALL_STATES.to_ne_bytes());
+    request.extend_from_slice(&[0; INET_DIAG_SOCKID_LEN]);
+
+    send(&socket, &request)?;
+
+    let connection_type = if protocol == libc::IPPROTO_TCP as u8 {
+        ConnectionType::Tcp
+    } else {
+        ConnectionType::Udp
+    };
+
+    let mut sockets = Vec::new();
+    let mut buffer = vec![0u8; 32 * 1024];
+    loop {
+        let len = recv(&socket, &mut buffer)?;
+        let mut offset = 0;
+
+        while offset + NLMSG_HDR_LEN <= len {
+            let msg_len = read_u32(&buffer, offset) as usize;
+            let msg_type = read_u16(&buffer, offset + 4);
+            if msg_len < NLMSG_HDR_LEN || offset + msg_len > len {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "truncated netlink message",
+                ));
+            }
+
+            match msg_type as i32 {
+                libc::NLMSG_DONE => return Ok(sockets),
+                libc::NLMSG_ERROR => return Err(netlink_error(&buffer[offset..offset + msg_len])),
+                _ => {
+                    let payload = &buffer[offset + NLMSG_HDR_LEN..offset + msg_len];
+                    if let Some(socket) = parse_diag_msg(payload, connection_type) {
+                        sockets.push(socket);
+                    }
+                }
+            }
+
+            offset += align(msg_len);
+        }
+    }
+}
+
+/// Aborts the TCP socket `local` -> `remote` with `SOCK_DESTROY`, the peer
+/// sees a reset. Needs `CAP_NET_ADMIN` and a kernel built with
+/// `CONFIG_INET_DIAG_DESTROY`; fails with `ENOENT` if there is no such socket.
+pub fn destroy_tcp_socket(local: SocketAddr, remote: SocketAddr) -> io::Result<()> {
+    let family = match (local, remote) {
+        (SocketAddr::V4(_), SocketAddr::V4(_)) => libc::AF_INET,
+        (SocketAddr::V6(_), SocketAddr::V6(_)) => libc::AF_INET6,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "local and remote address families differ",
+            ))
+        }
+    };
+
+    let to_bytes = |address: SocketAddr| -> [u8; 16] {
+        let mut bytes = [0; 16];
+        match address.ip() {
+            IpAddr::V4(ip) => bytes[..4].copy_from_slice(&ip.octets()),
+            IpAddr::V6(ip) => bytes = ip.octets(),
+        }
+        bytes
+    };
+
+    let socket = open_netlink()?;
+
+    let mut request = netlink_header(
+        NLMSG_HDR_LEN + INET_DIAG_REQ_V2_LEN,
+        SOCK_DESTROY,
+        (libc::NLM_F_REQUEST | libc::NLM_F_ACK) as u16,
+    );
+    request.push(family as u8);
+    request.push(libc::IPPROTO_TCP as u8);
+    request.push(0);
+    request.push(0);
+    request.extend_from_slice(&ALL_STATES.to_ne_bytes());
+    request.extend_from_slice(&local.port().to_be_bytes());
+    request.extend_from_slice(&remote.port().to_be_bytes());
+    request.extend_from_slice(&to_bytes(local));
+    request.extend_from_slice(&to_bytes(remote));
+    request.extend_from_slice(&0u32.to_ne_bytes());
+    request.extend_from_slice(&NO_COOKIE.to_ne_bytes());
+    request.extend_from_slice(&NO_COOKIE.to_ne_bytes());
+
+    send(&socket, &request)?;
+
+    let mut buffer = vec![0u8; 4096];
+    let len = recv(&socket, &mut buffer)?;
+    if len < NLMSG_HDR_LEN || read_u16(&buffer, 4) as i32 != libc::NLMSG_ERROR {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected netlink reply",
+        ));
+    }
+    // an NLMSG_ERROR carrying errno 0 is the ack
+    match netlink_error(&buffer[..len]) {
+        err if err.raw_os_error() == Some(0) => Ok(()),
+        err => Err(err),
+    }
+}
+
+fn parse_diag_msg(payload: &[u8], connection_type: ConnectionType) -> Option<DiagSocket> {
+    if payload.len() < INET_DIAG_MSG_LEN {
+        return None;
+    }
+
+    let family = payload[0];
+    let state = payload[1];
+    let (local, remote) = parse_sockid(family, &payload[4..4 + INET_DIAG_SOCKID_LEN])?;
+
+    let tail = 4 + INET_DIAG_SOCKID_LEN;
+    let rqueue = read_u32(payload, tail + 4);
+    let wqueue = read_u32(payload, tail + 8);
+    let uid = read_u32(payload, tail + 12);
+    let inode = read_u32(payload, tail + 16);
+
+    let mut info = TcpInfo {
+        uid,
+        ..Default::default()
+    };
+
+    let mut offset = INET_DIAG_MSG_LEN;
+    while offset + 4 <= payload.len() {
+        let attr_len = read_u16(payload, offset) as usize;
+        let attr_type = read_u16(payload, offset + 2);
+        if attr_len < 4 || offset + attr_len > payload.len() {
+            break;
+        }
+        if attr_type == INET_DIAG_INFO {
+            parse_tcp_info(&payload[offset + 4..offset + attr_len], &mut info);
+        }
+        offset += align(attr_len);
+    }
+
+    Some(DiagSocket {
+        connection_type,
+        local,
+        remote,
+        state,
+        inode,
+        send_queue: wqueue,
+        recv_queue: rqueue,
+        info,
+    })
+}
+
+/// Reads the fields we care about out of `struct tcp_info`. Older kernels send
+/// a shorter struct, in which case the newer counters stay at zero.
+fn parse_tcp_info(data: &[u8], info: &mut TcpInfo) {
+    let field = |offset: usize| {
+        if data.len() >= offset + 4 {
+            read_u32(data, offset)
+        } else {
+            0
+        }
+    };
+    let field64 = |offset: usize| {
+        if data.len() >= offset + 8 {
+            u64::from_ne_bytes(data[offset..offset + 8].try_into().unwrap())
+        } else {
+            0
+        }
+    };
+
+    info.rtt_us = field(68);
+    info.rtt_var_us = field(72);
+    info.snd_cwnd = field(80);
+    info.retransmits = field(100);
+    info.bytes_acked = field64(120);
+    info.bytes_received = field64(128);
+}
+
+fn parse_sockid(family: u8, id: &[u8]) -> Option<(SocketAddr, SocketAddr)> {
+    let sport = u16::from_be_bytes([id[0], id[1]]);
+    let dport = u16::from_be_bytes([id[2], id[3]]);
+    let src: [u8; 16] = id[4..20].try_into().unwrap();
+    let dst: [u8; 16] = id[20..36].try_into().unwrap();
+
+    let to_ip = |bytes: [u8; 16]| -> Option<IpAddr> {
+        match family as i32 {
+            libc::AF_INET => Some(IpAddr::V4(Ipv4Addr::new(
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ))),
+            libc::AF_INET6 => Some(IpAddr::V6(Ipv6Addr::from(bytes))),
+            _ => None,
+        }
+    };
+
+    Some((
+        SocketAddr::new(to_ip(src)?, sport),
+        SocketAddr::new(to_ip(dst)?, dport),
+    ))
+}
+
+pub(crate) fn open_netlink() -> io::Result<OwnedFd> {
+    // SAFETY: plain socket(2) call, the returned descriptor is owned below.
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `fd` is a freshly created, valid descriptor.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+pub(crate) fn netlink_header(len: usize, msg_type: u16, flags: u16) -> Vec<u8> {
+    let mut header = Vec::with_capacity(len);
+    header.extend_from_slice(&(len as u32).to_ne_bytes());
+    header.extend_from_slice(&msg_type.to_ne_bytes());
+    header.extend_from_slice(&flags.to_ne_bytes());
+    header.extend_from_slice(&1u32.to_ne_bytes());
+    header.extend_from_slice(&0u32.to_ne_bytes());
+    header
+}
+
+pub(crate) fn send(socket: &OwnedFd, request: &[u8]) -> io::Result<()> {
+    // SAFETY: an all-zero sockaddr_nl addresses the kernel.
+    let mut address: libc::sockaddr_nl = unsafe { mem::zeroed() };
+    address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+
+    // SAFETY: `request` and `address` outlive the call and the lengths match.
+    let sent = unsafe {
+        libc::sendto(
+            socket.as_raw_fd(),
+            request.as_ptr().cast(),
+            request.len(),
+            0,
+            (&address as *const libc::sockaddr_nl).cast(),
+            mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+pub(crate) fn recv(socket: &OwnedFd, buffer: &mut [u8]) -> io::Result<usize> {
+    // SAFETY: `buffer` is valid for writes of `buffer.len()` bytes.
+    let len = unsafe {
+        libc::recv(
+            socket.as_raw_fd(),
+            buffer.as_mut_ptr().cast(),
+            buffer.len(),
+            0,
+        )
+    };
+    if len < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(len as usize)
+}
+
+/// Turns an `NLMSG_ERROR` message into the errno it carries.
+pub(crate) fn netlink_error(message: &[u8]) -> io::Error {
+    if message.len() < NLMSG_HDR_LEN + 4 {
+        return io::Error::new(io::ErrorKind::InvalidData, "truncated netlink error");
+    }
+    let errno = i32::from_ne_bytes(
+        message[NLMSG_HDR_LEN..NLMSG_HDR_LEN + 4]
+            .try_into()
+            .unwrap(),
+    );
+    io::Error::from_raw_os_error(-errno)
+}
+
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn test_tcp_sockets_loopback() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+
+        client.write_all(b"ping").unwrap();
+        let mut buf = [0u8; 4];
+        server.read_exact(&mut buf).unwrap();
+
+        let sockets = tcp_sockets().unwrap();
+
+        let listening = sockets
+            .iter()
+            .find(|socket| socket.local == listener.local_addr().unwrap())
+            .expect("listener not reported");
+        assert_eq!(
+            ConnectionState::from_tcp_state(listening.state),
+            Some(ConnectionState::Listen)
+        );
+
+        let established = sockets
+            .iter()
+            .find(|socket| {
+                socket.local == client.local_addr().unwrap()
+                    && socket.remote == client.peer_addr().unwrap()
+            })
+            .expect("client socket not reported");
+        assert_eq!(
+            ConnectionState::from_tcp_state(established.state),
+            Some(ConnectionState::Established)
+        );
+        assert!(established.info.snd_cwnd > 0);
+        assert!(established.info.rtt_us > 0);
+        assert!(established.info.bytes_acked >= 1);
+        assert_eq!(established.info.uid, unsafe { libc::getuid() });
+    }
+
+    #[test]
+    fn test_attach_tcp_info() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let _server = listener.accept().unwrap();
+
+        let mut connections = vec![Connection::new(
+            ConnectionType::Tcp,
+            client.local_addr().unwrap().to_string(),
+            client.peer_addr().unwrap().to_string(),
+            ConnectionState::Established,
+            std::process::id().to_string(),
+        )];
+
+        attach_tcp_info(&mut connections, &tcp_sockets().unwrap());
+
+        assert!(connections[0].tcp_info.is_some());
+    }
+
+    #[test]
+    fn test_connections_resolves_own_pid() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let connections = connections().unwrap();
+        let own = connections
+            .iter()
+            .find(|connection| connection.local_address == address)
+            .expect("listener not reported");
+
+        assert_eq!(own.pid, std::process::id().to_string());
+        assert!(own.tcp_info.is_some());
+    }
+}
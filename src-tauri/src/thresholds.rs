@@ -0,0 +1,395 @@
+//! Rate based thresholds over the `netstat -s` counters.
+//!
+//! Thresholds live next to the alerting rules, one `[[threshold]]` table
+//! each. `counter` (and `per`) are dotted paths into the serialized
+//! `StatsSnapshot`. Without `per` the limit is in events per second,
+//! with it the limit is a ratio of the two deltas:
+//!
+//! ```toml
+//! [[threshold]]
+//! name = "TCP retransmits"
+//! counter = "tcp_ipv4.segments_retransmitted"
+//! per = "tcp_ipv4.segments_sent"
+//! above = 0.02
+//! window = 60
+//!
+//! [[threshold]]
+//! name = "Interface errors"
+//! counter = "interface.errors.received"
+//! above = 1
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+fn default_window() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Threshold {
+    pub name: String,
+    pub counter: String,
+    /// Makes the limit a ratio of `counter` to this counter.
+    pub per: Option<String>,
+    pub above: f64,
+    /// Seconds.
+    #[serde(default = "default_window")]
+    pub window: u64,
+}
+
+#[derive(Deserialize)]
+struct ThresholdFile {
+    #[serde(default)]
+    threshold: Vec<Threshold>,
+}
+
+/// Parses the `[[threshold]]` tables of a rules file, everything else in it
+/// is ignored.
+pub fn parse_thresholds(input: &str) -> Result<Vec<Threshold>, String> {
+    let file: ThresholdFile = toml::from_str(input).map_err(|err| err.to_string())?;
+
+    for threshold in &file.threshold {
+        if threshold.window == 0 {
+            return Err(format!("threshold {:?} has no window", threshold.name));
+        }
+        if threshold.above < 0.0 {
+            return Err(format!(
+                "threshold {:?} has a negative limit",
+                threshold.name
+            ));
+        }
+    }
+    Ok(file.threshold)
+}
+
+/// Looks up `tcp_ipv4.segments_sent`-style paths in serialized statistics.
+pub fn counter_value(stats: &Value, path: &str) -> Option<u64> {
+    path.split('.')
+        .try_fold(stats, |value, key| value.get(key))
+        .and_then(Value::as_u64)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThresholdEvent {
+    pub name: String,
+    pub counter: String,
+    pub per: Option<String>,
+    /// Top level section of the counter, i.e. the card it is shown on.
+    pub card: String,
+    pub value: f64,
+    pub above: f64,
+    /// `false` once the value drops back under the limit.
+    pub crossed: bool,
+    pub timestamp: u64,
+}
+
+impl ThresholdEvent {
+    /// One line summary, used as the notification body.
+    pub fn summary(&self) -> String {
+        let state = if self.crossed { "above" } else { "back under" };
+        match &self.per {
+            Some(per) => format!(
+                "{} is {:.2}% of {}, {} {:.2}%",
+                self.counter,
+                self.value * 100.0,
+                per,
+                state,
+                self.above * 100.0
+            ),
+            None => format!(
+                "{} at {:.2}/s, {} {:.2}/s",
+                self.counter, self.value, state, self.above
+            ),
+        }
+    }
+}
+
+struct Sample {
+    at: Instant,
+    counter: u64,
+    per: Option<u64>,
+}
+
+struct Tracker {
+    threshold: Threshold,
+    samples: VecDeque<Sample>,
+    crossed: bool,
+}
+
+impl Tracker {
+    /// Records a sample and returns the rate (or ratio) over the window, once
+    /// a full window has been seen.
+    fn sample(&mut self, stats: &Value, now: Instant) -> Option<f64> {
+        let counter = counter_value(stats, &self.threshold.counter)?;
+        let per = match &self.threshold.per {
+            Some(path) => Some(counter_value(stats, path)?),
+            None => None,
+        };
+
+        // counters went backwards (interface reset, stats source changed),
+        // start over
+        if let Some(last) = self.samples.back() {
+            if counter < last.counter || per < last.per {
+                self.samples.clear();
+            }
+        }
+        self.samples.push_back(Sample {
+            at: now,
+            counter,
+            per,
+        });
+
+        // keep exactly one sample that is at least a window old as the base
+        let window = Duration::from_secs(self.threshold.window);
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|next| now.duration_since(next.at) >= window)
+        {
+            self.samples.pop_front();
+        }
+
+        let base = self.samples.front()?;
+        let elapsed = now.duration_since(base.at);
+        if elapsed < window {
+            return None;
+        }
+
+        let delta = (counter - base.counter) as f64;
+        Some(match (per, base.per) {
+            (Some(per), Some(base_per)) if per > base_per => delta / (per - base_per) as f64,
+            (Some(_), _) => 0.0,
+            (None, _) => delta / elapsed.as_secs_f64(),
+        })
+    }
+}
+
+/// Tracks every threshold over successive statistics samples and reports
+/// when one is crossed, or drops back under its limit.
+#[derive(Default)]
+pub struct ThresholdEngine {
+    trackers: Vec<Tracker>,
+}
+
+impl ThresholdEngine {
+    pub fn new(thresholds: Vec<Threshold>) -> Self {
+        let mut engine = ThresholdEngine::default();
+        engine.set_thresholds(thresholds);
+        engine
+    }
+
+    /// Replaces the thresholds, dropping all history.
+    pub fn set_thresholds(&mut self, thresholds: Vec<Threshold>) {
+        self.trackers = thresholds
+            .into_iter()
+            .map(|threshold| Tracker {
+                threshold,
+                samples: VecDeque::new(),
+                crossed: false,
+            })
+            .collect();
+    }
+
+    /// Feeds one statistics sample taken at `now`, returns the thresholds that
+    /// changed state.
+    pub fn evaluate(&mut self, stats: &Value, now: Instant) -> Vec<ThresholdEvent> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        let mut events = Vec::new();
+        for tracker in &mut self.trackers {
+            let Some(value) = tracker.sample(stats, now) else {
+                continue;
+            };
+            let crossed = value > tracker.threshold.above;
+            if crossed == tracker.crossed {
+                continue;
+            }
+            tracker.crossed = crossed;
+
+            let threshold = &tracker.threshold;
+            events.push(ThresholdEvent {
+                name: threshold.name.clone(),
+                counter: threshold.counter.clone(),
+                per: threshold.per.clone(),
+                card: threshold
+                    .counter
+                    .split('.')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                value,
+                above: threshold.above,
+                crossed,
+                timestamp,
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const THRESHOLDS: &str = r#"
+[[rule]]
+name = "SMTP"
+port = 25
+
+[[threshold]]
+name = "TCP retransmits"
+counter = "tcp_ipv4.segments_retransmitted"
+per = "tcp_ipv4.segments_sent"
+above = 0.02
+
+[[threshold]]
+name = "Interface errors"
+counter = "interface.errors.received"
+above = 1
+window = 10
+"#;
+
+    fn stats(sent: u64, retransmitted: u64, errors: u64) -> Value {
+        json!({
+            "interface": { "errors": { "received": errors, "sent": 0 } },
+            "tcp_ipv4": {
+                "segments_sent": sent,
+                "segments_retransmitted": retransmitted,
+            },
+        })
+    }
+
+    #[test]
+    fn test_parse_thresholds() {
+        let thresholds = parse_thresholds(THRESHOLDS).unwrap();
+        assert_eq!(thresholds.len(), 2);
+        assert_eq!(thresholds[0].window, 60);
+        assert_eq!(thresholds[0].per.as_deref(), Some("tcp_ipv4.segments_sent"));
+        assert_eq!(thresholds[1].above, 1.0);
+
+        assert!(parse_thresholds("").unwrap().is_empty());
+        assert!(parse_thresholds(
+            "[[threshold]]\nname = \"x\"\ncounter = \"a.b\"\nabove = 1\nwindow = 0\n"
+        )
+        .is_err());
+        assert!(parse_thresholds("[[threshold]]\nname = \"x\"\ncounter = \"a.b\"\n").is_err());
+    }
+
+    #[test]
+    fn test_counter_value() {
+        let stats = stats(1000, 10, 3);
+        assert_eq!(counter_value(&stats, "interface.errors.received"), Some(3));
+        assert_eq!(counter_value(&stats, "interface.errors"), None);
+        assert_eq!(counter_value(&stats, "tcp_ipv6.segments_sent"), None);
+
+        let parsed = serde_json::to_value(crate::parse_stats("")).unwrap();
+        for path in [
+            "tcp_ipv4.segments_retransmitted",
+            "tcp_ipv4.failed_connection_attempts",
+            "ipv4.received_header_errors",
+            "interface.discards.received",
+        ] {
+            assert_eq!(counter_value(&parsed, path), Some(0), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_ratio_threshold() {
+        let mut engine = ThresholdEngine::new(parse_thresholds(THRESHOLDS).unwrap());
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(engine.evaluate(&stats(1000, 10, 0), at(0)).is_empty());
+        // 5% retransmits, but the window is not full yet
+        assert!(engine.evaluate(&stats(2000, 60, 0), at(30)).is_empty());
+
+        let events = engine.evaluate(&stats(3000, 70, 0), at(60));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "TCP retransmits");
+        assert_eq!(events[0].card, "tcp_ipv4");
+        assert!(events[0].crossed);
+        assert!((events[0].value - 0.03).abs() < 1e-9);
+        assert_eq!(
+            events[0].summary(),
+            "tcp_ipv4.segments_retransmitted is 3.00% of tcp_ipv4.segments_sent, above 2.00%"
+        );
+
+        // still above, nothing new to report
+        assert!(engine.evaluate(&stats(4000, 100, 0), at(80)).is_empty());
+
+        // the window is now 30s..90s: 40 retransmits over 3000 segments
+        let events = engine.evaluate(&stats(5000, 100, 0), at(90));
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].crossed);
+    }
+
+    #[test]
+    fn test_rate_threshold() {
+        let mut engine = ThresholdEngine::new(parse_thresholds(THRESHOLDS).unwrap());
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(engine.evaluate(&stats(0, 0, 100), at(0)).is_empty());
+        assert!(engine.evaluate(&stats(0, 0, 105), at(10)).is_empty());
+
+        let events = engine.evaluate(&stats(0, 0, 130), at(20));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].card, "interface");
+        assert_eq!(
+            events[0].summary(),
+            "interface.errors.received at 2.50/s, above 1.00/s"
+        );
+
+        // a counter reset starts a new window instead of alerting
+        assert!(engine.evaluate(&stats(0, 0, 0), at(25)).is_empty());
+        let events = engine.evaluate(&stats(0, 0, 0), at(35));
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].crossed);
+    }
+
+    #[test]
+    fn test_proc_net_dev_threshold() {
+        // Linux has no `netstat -e`, the interface section is summed over
+        // /proc/net/dev instead
+        let snapshot = |eth0_errors: u64| {
+            let input = format!(
+                "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 8302212   79054    0    0    0     0          0         0  8302212   79054    0    0    0     0       0          0
+  eth0:1893449342 1527811 {}   12    0     0          0      1204 103441125  784112    0    1    0     0       0          0
+",
+                eth0_errors
+            );
+            let input = crate::format_proc_net_files(&[("dev", input)]);
+            serde_json::to_value(crate::parse_proc_net_stats(&input).0).unwrap()
+        };
+
+        let stats = snapshot(3);
+        assert_eq!(counter_value(&stats, "interface.errors.received"), Some(3));
+        assert_eq!(
+            counter_value(&stats, "interface.discards.received"),
+            Some(12)
+        );
+        assert_eq!(
+            counter_value(&stats, "interface.bytes.sent"),
+            Some(111743337)
+        );
+
+        let mut engine = ThresholdEngine::new(parse_thresholds(THRESHOLDS).unwrap());
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        assert!(engine.evaluate(&stats, at(0)).is_empty());
+        let events = engine.evaluate(&snapshot(53), at(10));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "Interface errors");
+        assert!(events[0].crossed);
+    }
+}
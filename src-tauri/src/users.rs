@@ -0,0 +1,285 @@
+//! Processes and their connections by owning user or by session, for shared
+//! terminal servers where the question is whose processes are talking.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{group_connections_by_pid, Connection, Process};
+
+/// Group of the processes whose owner the source does not report, or we are
+/// not allowed to see.
+pub const UNKNOWN: &str = "unknown";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    User,
+    Session,
+}
+
+/// Which processes to keep, fields left at `None` match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ProcessFilter {
+    /// `alice` matches `alice` as well as `CORP\alice`, case-insensitively.
+    pub user: Option<String>,
+    /// Session name (`Console`, `RDP-Tcp#3`) or number.
+    pub session: Option<String>,
+}
+
+impl ProcessFilter {
+    pub fn matches(&self, process: &Process) -> bool {
+        let user = self.user.as_ref().is_none_or(|wanted| {
+            process.user.as_deref().is_some_and(|user| {
+                let name = user.rsplit_once('\\').map_or(user, |(_, name)| name);
+                user.eq_ignore_ascii_case(wanted) || name.eq_ignore_ascii_case(wanted)
+            })
+        });
+        let session = self.session.as_ref().is_none_or(|wanted| {
+            process.session_number == *wanted || process.session_name.eq_ignore_ascii_case(wanted)
+        });
+        user && session
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessGroup {
+    /// The user name, or the session as `session_key` spells it.
+    pub key: String,
+    pub processes: Vec<Process>,
+    pub connections: Vec<Connection>,
+}
+
+/// `Console (1)` on Windows; Linux has no session names, only the number.
+pub fn session_key(process: &Process) -> String {
+    if process.session_name.is_empty() {
+        process.session_number.clone()
+    } else {
+        format!("{} ({})", process.session_name, process.session_number)
+    }
+}
+
+/// The processes matching `filter` grouped by user or session, each group
+/// with the connections its processes hold. Groups with the most connections
+/// come first.
+pub fn group_processes(
+    processes: Vec<Process>,
+    connections: Vec<Connection>,
+    by: GroupBy,
+    filter: &ProcessFilter,
+) -> Vec<ProcessGroup> {
+    let mut connections_by_pid = group_connections_by_pid(connections);
+    let mut groups: BTreeMap<String, ProcessGroup> = BTreeMap::new();
+
+    for process in processes
+        .into_iter()
+        .filter(|process| filter.matches(process))
+    {
+        let key = match by {
+            GroupBy::User => process.user.clone().unwrap_or_else(|| UNKNOWN.to_string()),
+            GroupBy::Session => session_key(&process),
+        };
+        let group = groups.entry(key.clone()).or_insert_with(|| ProcessGroup {
+            key,
+            processes: Vec::new(),
+            connections: Vec::new(),
+        });
+        if let Some(connections) = connections_by_pid.remove(&process.pid) {
+            group.connections.extend(connections);
+        }
+        group.processes.push(process);
+    }
+
+    let mut groups: Vec<ProcessGroup> = groups.into_values().collect();
+    groups.sort_by_key(|group| std::cmp::Reverse(group.connections.len()));
+    groups
+}
+
+/// Sets `user` to the owner of the access token of each process, as
+/// `DOMAIN\user`, for `tasklist /fo csv` which does not report it. Processes
+/// we are not allowed to open are left at `None`.
+#[cfg(windows)]
+pub fn attach_token_users(processes: &mut [Process]) {
+    for process in processes.iter_mut() {
+        if process.user.is_none() {
+            process.user = process.pid.parse().ok().and_then(token_user);
+        }
+    }
+}
+
+#[cfg(windows)]
+fn token_user(pid: u32) -> Option<String> {
+    use std::{mem, ptr};
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::Security::{
+        GetTokenInformation, LookupAccountSidW, TokenUser, SID_NAME_USE, TOKEN_QUERY, TOKEN_USER,
+    };
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // SAFETY: every buffer is passed along with its size, the SID read from
+    // `buffer` is used while `buffer` lives, and both handles are closed
+    // before returning.
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle == 0 {
+            return None;
+        }
+        let mut token: HANDLE = 0;
+        let opened = OpenProcessToken(handle, TOKEN_QUERY, &mut token);
+        CloseHandle(handle);
+        if opened == 0 {
+            return None;
+        }
+
+        // TOKEN_USER is followed by the SID it points to, u64 for alignment
+        let mut buffer = [0u64; 64];
+        let mut length = 0;
+        let queried = GetTokenInformation(
+            token,
+            TokenUser,
+            buffer.as_mut_ptr().cast(),
+            mem::size_of_val(&buffer) as u32,
+            &mut length,
+        );
+        CloseHandle(token);
+        if queried == 0 {
+            return None;
+        }
+        let sid = (*buffer.as_ptr().cast::<TOKEN_USER>()).User.Sid;
+
+        let mut name = [0u16; 256];
+        let mut domain = [0u16; 256];
+        let mut name_length = name.len() as u32;
+        let mut domain_length = domain.len() as u32;
+        let mut kind: SID_NAME_USE = 0;
+        if LookupAccountSidW(
+            ptr::null(),
+            sid,
+            name.as_mut_ptr(),
+            &mut name_length,
+            domain.as_mut_ptr(),
+            &mut domain_length,
+            &mut kind,
+        ) == 0
+        {
+            return None;
+        }
+
+        let name = String::from_utf16_lossy(&name[..name_length as usize]);
+        let domain = String::from_utf16_lossy(&domain[..domain_length as usize]);
+        Some(if domain.is_empty() {
+            name
+        } else {
+            format!("{}\\{}", domain, name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionState, ConnectionType};
+
+    fn process(pid: &str, user: Option<&str>, session_name: &str, session_number: &str) -> Process {
+        let mut process = Process::new(format!("{}.exe", pid), pid.to_string());
+        process.session_name = session_name.to_string();
+        process.session_number = session_number.to_string();
+        process.user = user.map(str::to_string);
+        process
+    }
+
+    fn connection(pid: &str) -> Connection {
+        Connection::new(
+            ConnectionType::Tcp,
+            "10.0.0.5:50000".to_string(),
+            "140.82.112.4:443".to_string(),
+            ConnectionState::Established,
+            pid.to_string(),
+        )
+    }
+
+    fn processes() -> Vec<Process> {
+        vec![
+            process("4", Some("NT AUTHORITY\\SYSTEM"), "Services", "0"),
+            process("100", Some("CORP\\alice"), "RDP-Tcp#3", "2"),
+            process("101", Some("CORP\\alice"), "RDP-Tcp#3", "2"),
+            process("200", Some("CORP\\bob"), "RDP-Tcp#5", "3"),
+            process("300", None, "Services", "0"),
+        ]
+    }
+
+    #[test]
+    fn test_group_by_user() {
+        let connections = vec![
+            connection("100"),
+            connection("101"),
+            connection("101"),
+            connection("200"),
+            connection("999"),
+        ];
+
+        let groups = group_processes(
+            processes(),
+            connections,
+            GroupBy::User,
+            &ProcessFilter::default(),
+        );
+
+        let summary: Vec<(&str, usize, usize)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.key.as_str(),
+                    group.processes.len(),
+                    group.connections.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("CORP\\alice", 2, 3),
+                ("CORP\\bob", 1, 1),
+                ("NT AUTHORITY\\SYSTEM", 1, 0),
+                (UNKNOWN, 1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_session_with_filter() {
+        let groups = group_processes(
+            processes(),
+            vec![connection("200")],
+            GroupBy::Session,
+            &ProcessFilter::default(),
+        );
+        let keys: Vec<&str> = groups.iter().map(|group| group.key.as_str()).collect();
+        assert_eq!(keys, ["RDP-Tcp#5 (3)", "RDP-Tcp#3 (2)", "Services (0)"]);
+
+        let alice = ProcessFilter {
+            user: Some("ALICE".to_string()),
+            session: None,
+        };
+        let groups = group_processes(processes(), Vec::new(), GroupBy::Session, &alice);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].processes.len(), 2);
+
+        let services = ProcessFilter {
+            user: None,
+            session: Some("0".to_string()),
+        };
+        let pids: Vec<String> = processes()
+            .into_iter()
+            .filter(|process| services.matches(process))
+            .map(|process| process.pid)
+            .collect();
+        assert_eq!(pids, ["4", "300"]);
+
+        let linux = process("1834", Some("alice"), "", "1790");
+        assert_eq!(session_key(&linux), "1790");
+        assert!(alice.matches(&linux));
+    }
+}
@@ -1,7 +1,16 @@
 import { useMemo, useState } from "react";
 
 import { COLUMNS } from "./components/connections/columns";
+import { ConfirmAction } from "./components/connections/confirm-action";
 import { DataTable } from "./components/connections/data-table";
+import { INTERFACE_COLUMNS } from "./components/interfaces/columns";
+import { LISTENING_COLUMNS } from "./components/listening/columns";
+import { ProcessDetailsPanel } from "./components/processes/process-details";
+import { ProcessTree } from "./components/processes/process-tree";
+import { NEIGHBOR_COLUMNS, ROUTE_COLUMNS } from "./components/routing/columns";
+import { ConnectionRoute } from "./components/routing/connection-route";
+import { ImportReports } from "./components/import/import-reports";
+import { ExtendedStatsCard } from "./components/stats/extended-stats-card";
 import {
   Accordion,
   AccordionContent,
@@ -10,6 +19,8 @@ import {
 } from "./components/ui/accordion";
 import { Button } from "./components/ui/button";
 import { Card, CardContent, CardHeader, CardTitle } from "./components/ui/card";
+import { Checkbox } from "./components/ui/checkbox";
+import { Label } from "./components/ui/label";
 import {
   Select,
   SelectContent,
@@ -19,16 +30,51 @@ import {
   SelectTrigger,
   SelectValue,
 } from "./components/ui/select";
+import { Toaster } from "./components/ui/toaster";
+import { killProcess } from "./hooks/use-actions";
+import { useImport } from "./hooks/use-import";
 import { useNetProcess } from "./hooks/use-net-process";
-import { NetStatChildKeys, NetStatKeys } from "./types";
+import { useProcessGroups } from "./hooks/use-process-groups";
+import { useStatFields } from "./hooks/use-stat-fields";
+import { useThresholdAlerts } from "./hooks/use-threshold-alerts";
+import { cn } from "./lib/utils";
+import { GroupBy, NetStatChildKeys, NetStatKeys } from "./types";
 import { prettyBytes, prettyHeading, prettyNumber } from "./utils/formatters";
 import { isOnlyObject } from "./utils/is-only-object";
 
 function App() {
   const [enabled, setEnabled] = useState(false);
-  const { netConnectionsResult, netStatResult, processesResult } =
-    useNetProcess(enabled);
+  const {
+    netConnectionsResult,
+    connectionsByContainerResult,
+    netStatResult,
+    netInterfacesResult,
+    processesResult,
+    processTreeResult,
+    listeningPortsResult,
+    routesResult,
+    neighborsResult,
+  } = useNetProcess(enabled);
+  const { alertsByCard } = useThresholdAlerts();
+  const { statFields } = useStatFields();
+  const { reports, error: importError, openFile, closeFiles } = useImport();
   const [selectedProcess, setSelectedProcess] = useState("");
+  const [externalOnly, setExternalOnly] = useState(false);
+  const [groupBy, setGroupBy] = useState<GroupBy>("user");
+  const { processGroups } = useProcessGroups(enabled, groupBy);
+
+  const selectedProcessInfo = processesResult.find(
+    (process) => process.pid.toString() === selectedProcess
+  );
+
+  const selectedConnections = useMemo(() => {
+    const connections = netConnectionsResult[selectedProcess];
+    if (!connections || !externalOnly) return connections;
+
+    return connections.filter(
+      (connection) => connection.foreign_class === "Public"
+    );
+  }, [netConnectionsResult, selectedProcess, externalOnly]);
 
   const processesWithConnections = useMemo(() => {
     return processesResult
@@ -53,9 +99,33 @@ function App() {
         Refresh ({enabled ? "on" : "off"})
       </Button>
 
+      <div className="flex items-center gap-2">
+        <Button type="button" variant="outline" onClick={openFile}>
+          Open file
+        </Button>
+        {reports.length ? (
+          <Button type="button" variant="outline" onClick={closeFiles}>
+            Back to this system
+          </Button>
+        ) : null}
+        {importError ? (
+          <p className="text-sm text-destructive">{importError}</p>
+        ) : null}
+      </div>
+      {reports.length ? <ImportReports reports={reports} /> : null}
+
       <Accordion
         type="multiple"
-        defaultValue={["item-1", "item-2"]}
+        defaultValue={[
+          "item-1",
+          "item-2",
+          "item-3",
+          "item-4",
+          "item-5",
+          "item-6",
+          "item-7",
+          "item-8",
+        ]}
         className="w-full"
       >
         <AccordionItem value="item-1">
@@ -63,16 +133,37 @@ function App() {
           <AccordionContent>
             {netStatResult ? (
               <div className="flex gap-4 overflow-x-auto">
-                {(Object.keys(netStatResult) as NetStatKeys[]).map((key) => (
-                  <Card key={key} className="min-w-max">
+                {(
+                  Object.keys(netStatResult).filter(
+                    (key) => key !== "extended"
+                  ) as NetStatKeys[]
+                ).map((key) => (
+                  <Card
+                    key={key}
+                    className={cn(
+                      "min-w-max",
+                      alertsByCard[key] && "border-destructive"
+                    )}
+                  >
                     <CardHeader>
                       <CardTitle>{prettyHeading(key)}</CardTitle>
+                      {alertsByCard[key]?.map((alert) => (
+                        <p
+                          key={alert.name}
+                          className="text-xs font-medium text-destructive"
+                        >
+                          {alert.name}
+                        </p>
+                      ))}
                     </CardHeader>
                     <CardContent>
                       {(
                         Object.keys(netStatResult[key]) as NetStatChildKeys[]
                       ).map((key2) => (
-                        <div key={key2}>
+                        <div
+                          key={key2}
+                          title={statFields[key]?.[key2]?.description}
+                        >
                           <div className="font-bold">{prettyHeading(key2)}</div>
                           <p className="text-xs text-muted-foreground">
                             {isOnlyObject(netStatResult[key][key2]) &&
@@ -85,7 +176,9 @@ function App() {
                                     netStatResult[key][key2].received
                                   )).toLocaleString()}`}
                                 >
-                                  {prettyBytes(
+                                  {(statFields[key]?.[key2]?.unit === "bytes"
+                                    ? prettyBytes
+                                    : prettyNumber)(
                                     // @ts-expect-error - I know what I'm doing
                                     netStatResult[key][key2].received
                                   )}
@@ -97,13 +190,17 @@ function App() {
                                     netStatResult[key][key2].sent
                                   )).toLocaleString()}`}
                                 >
-                                  {/* @ts-expect-error - I know what I'm doing */}
-                                  {prettyBytes(netStatResult[key][key2].sent)}
+                                  {(statFields[key]?.[key2]?.unit === "bytes"
+                                    ? prettyBytes
+                                    : prettyNumber)(
+                                    // @ts-expect-error - I know what I'm doing
+                                    netStatResult[key][key2].sent
+                                  )}
                                 </span>
                               </span>
                             ) : (
                               <span className="tabular-nums">
-                                {prettyNumber(netStatResult[key][key2])}
+                                {prettyNumber(netStatResult[key][key2] as number)}
                               </span>
                             )}
                           </p>
@@ -112,11 +209,108 @@ function App() {
                     </CardContent>
                   </Card>
                 ))}
+                {netStatResult.extended ? (
+                  <ExtendedStatsCard
+                    stats={netStatResult.extended}
+                    alerts={alertsByCard.extended}
+                  />
+                ) : null}
               </div>
             ) : null}
           </AccordionContent>
         </AccordionItem>
 
+        <AccordionItem value="item-5">
+          <AccordionTrigger>Interfaces</AccordionTrigger>
+          <AccordionContent>
+            <DataTable
+              columns={INTERFACE_COLUMNS}
+              data={netInterfacesResult}
+            />
+          </AccordionContent>
+        </AccordionItem>
+
+        <AccordionItem value="item-6">
+          <AccordionTrigger>Routes and neighbors</AccordionTrigger>
+          <AccordionContent className="space-y-4">
+            <DataTable columns={ROUTE_COLUMNS} data={routesResult} />
+            <DataTable columns={NEIGHBOR_COLUMNS} data={neighborsResult} />
+          </AccordionContent>
+        </AccordionItem>
+
+        <AccordionItem value="item-4">
+          <AccordionTrigger>Listening ports</AccordionTrigger>
+          <AccordionContent>
+            <DataTable
+              columns={LISTENING_COLUMNS}
+              data={listeningPortsResult}
+            />
+          </AccordionContent>
+        </AccordionItem>
+
+        <AccordionItem value="item-7">
+          <AccordionTrigger>Containers</AccordionTrigger>
+          <AccordionContent className="space-y-4">
+            {Object.entries(connectionsByContainerResult).map(
+              ([container, connections]) => (
+                <div key={container} className="space-y-2">
+                  <div className="font-bold" title={container}>
+                    {container === "host" ? "Host" : container.slice(0, 12)}{" "}
+                    <span className="text-xs font-normal text-muted-foreground">
+                      ({connections.length} connections)
+                    </span>
+                  </div>
+                  <DataTable columns={COLUMNS} data={connections} />
+                </div>
+              )
+            )}
+          </AccordionContent>
+        </AccordionItem>
+
+        <AccordionItem value="item-8">
+          <AccordionTrigger>Users and sessions</AccordionTrigger>
+          <AccordionContent className="space-y-4">
+            <Select
+              value={groupBy}
+              onValueChange={(value) => setGroupBy(value as GroupBy)}
+            >
+              <SelectTrigger className="w-48">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="user">By user</SelectItem>
+                <SelectItem value="session">By session</SelectItem>
+              </SelectContent>
+            </Select>
+
+            {processGroups.map((group) => (
+              <div key={group.key} className="space-y-2">
+                <div className="font-bold">
+                  {group.key}{" "}
+                  <span className="text-xs font-normal text-muted-foreground">
+                    ({group.processes.length} processes,{" "}
+                    {group.connections.length} connections)
+                  </span>
+                </div>
+                {group.connections.length ? (
+                  <DataTable columns={COLUMNS} data={group.connections} />
+                ) : null}
+              </div>
+            ))}
+          </AccordionContent>
+        </AccordionItem>
+
+        <AccordionItem value="item-3">
+          <AccordionTrigger>Process tree</AccordionTrigger>
+          <AccordionContent>
+            <ProcessTree
+              nodes={processTreeResult}
+              selectedPid={selectedProcess}
+              onSelect={setSelectedProcess}
+            />
+          </AccordionContent>
+        </AccordionItem>
+
         <AccordionItem value="item-2">
           <AccordionTrigger>Connections</AccordionTrigger>
           <AccordionContent>
@@ -143,15 +337,47 @@ function App() {
               </SelectContent>
             </Select>
 
-            {netConnectionsResult[selectedProcess] ? (
+            <div className="flex items-center space-x-2 pt-4">
+              <Checkbox
+                id="external-only"
+                checked={externalOnly}
+                onCheckedChange={(value) => setExternalOnly(!!value)}
+              />
+              <Label htmlFor="external-only">External only</Label>
+
+              {selectedProcess ? (
+                <ConfirmAction
+                  title="Terminate process?"
+                  description={`This terminates ${
+                    selectedProcessInfo?.image_name ?? "process"
+                  } (PID ${selectedProcess}). Unsaved work in it will be lost.`}
+                  confirmLabel="Terminate"
+                  onConfirm={() => killProcess(selectedProcess)}
+                >
+                  <Button variant="destructive" size="sm" className="ml-auto">
+                    Kill process
+                  </Button>
+                </ConfirmAction>
+              ) : null}
+            </div>
+
+            {selectedConnections ? (
               <DataTable
                 columns={COLUMNS}
-                data={netConnectionsResult[selectedProcess]}
+                data={selectedConnections}
+                renderSubComponent={(row) => (
+                  <>
+                    <ProcessDetailsPanel pid={row.original.pid} />
+                    <ConnectionRoute address={row.original.foreign_address} />
+                  </>
+                )}
               />
             ) : null}
           </AccordionContent>
         </AccordionItem>
       </Accordion>
+
+      <Toaster />
     </div>
   );
 }
@@ -1,9 +1,31 @@
 import { ColumnDef } from "@tanstack/react-table";
-import { ArrowUpDown } from "lucide-react";
+import { ArrowUpDown, XCircle } from "lucide-react";
 
 import { Button } from "@/components/ui/button";
+import { closeConnection } from "@/hooks/use-actions";
+import { cn } from "@/lib/utils";
 import { Connection } from "@/types";
 
+import { ConfirmAction } from "./confirm-action";
+
+// Queues above this many bytes usually mean the consumer is falling behind
+export const QUEUE_WARNING_THRESHOLD = 64 * 1024;
+
+function QueueCell({ value }: { value: number | null }) {
+  if (value === null) return null;
+
+  return (
+    <span
+      className={cn(
+        "tabular-nums",
+        value >= QUEUE_WARNING_THRESHOLD && "font-bold text-destructive"
+      )}
+    >
+      {value.toLocaleString()}
+    </span>
+  );
+}
+
 export const COLUMNS: ColumnDef<Connection>[] = [
   {
     header: ({ column }) => {
@@ -47,6 +69,86 @@ export const COLUMNS: ColumnDef<Connection>[] = [
     },
     accessorKey: "foreign_address",
   },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Hostname
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "foreign_hostname",
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Scope
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "foreign_class",
+    cell: ({ row }) =>
+      row.original.foreign_mapped
+        ? `${row.original.foreign_class} (IPv4-mapped)`
+        : row.original.foreign_class,
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Service
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "service",
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Country
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    id: "country",
+    accessorFn: (row) => row.geo?.country_code,
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Organization
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    id: "organization",
+    accessorFn: (row) => row.geo?.organization,
+    cell: ({ row }) =>
+      row.original.geo?.asn
+        ? `AS${row.original.geo.asn} ${row.original.geo.organization ?? ""}`
+        : null,
+  },
   {
     header: ({ column }) => {
       return (
@@ -75,4 +177,94 @@ export const COLUMNS: ColumnDef<Connection>[] = [
     },
     accessorKey: "pid",
   },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Send-Q
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "send_queue",
+    cell: ({ row }) => <QueueCell value={row.original.send_queue} />,
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Recv-Q
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "recv_queue",
+    cell: ({ row }) => <QueueCell value={row.original.recv_queue} />,
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          RTT
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    id: "rtt",
+    accessorFn: (row) => row.tcp_info?.rtt_us,
+    cell: ({ row }) =>
+      row.original.tcp_info
+        ? `${(row.original.tcp_info.rtt_us / 1000).toFixed(2)} ms`
+        : null,
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Retransmits
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    id: "retransmits",
+    accessorFn: (row) => row.tcp_info?.retransmits,
+  },
+  {
+    id: "actions",
+    enableHiding: false,
+    cell: ({ row }) => {
+      const { connection_type, state, local_address, foreign_address } =
+        row.original;
+      if (connection_type !== "Tcp" || state !== "Established") return null;
+
+      // keep clicks (including ones inside the portaled dialog) from
+      // toggling the row
+      return (
+        <div onClick={(event) => event.stopPropagation()}>
+          <ConfirmAction
+            title="Close connection?"
+            description={`This resets the TCP connection ${local_address} -> ${foreign_address}. The process that owns it will see the connection drop.`}
+            confirmLabel="Close connection"
+            onConfirm={() => closeConnection(local_address, foreign_address)}
+          >
+            <Button variant="ghost" size="icon" title="Close connection">
+              <XCircle className="h-4 w-4" />
+            </Button>
+          </ConfirmAction>
+        </div>
+      );
+    },
+  },
 ];
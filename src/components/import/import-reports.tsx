@@ -0,0 +1,36 @@
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { ImportReport } from "@/types";
+
+/** What was read from each imported file, and the lines that were not. */
+export function ImportReports({ reports }: { reports: ImportReport[] }) {
+  return (
+    <div className="flex gap-4 overflow-x-auto">
+      {reports.map((report) => (
+        <Card key={report.name} className="min-w-max">
+          <CardHeader>
+            <CardTitle>{report.name}</CardTitle>
+            <p className="text-xs text-muted-foreground">
+              {report.format}: {report.parsed} rows, {report.skipped.length}{" "}
+              lines skipped
+            </p>
+          </CardHeader>
+          {report.skipped.length ? (
+            <CardContent className="max-h-48 overflow-y-auto">
+              {report.skipped.map((skipped) => (
+                <div
+                  key={skipped.line}
+                  className="grid grid-cols-[3rem_1fr] gap-x-2 font-mono text-xs"
+                >
+                  <span className="text-right text-muted-foreground">
+                    {skipped.line}
+                  </span>
+                  <span className="whitespace-pre">{skipped.text}</span>
+                </div>
+              ))}
+            </CardContent>
+          ) : null}
+        </Card>
+      ))}
+    </div>
+  );
+}
@@ -0,0 +1,106 @@
+import { ColumnDef } from "@tanstack/react-table";
+import { ArrowUpDown } from "lucide-react";
+
+import { Button } from "@/components/ui/button";
+import { cn } from "@/lib/utils";
+import { NetStatValue, NetworkInterface } from "@/types";
+import { prettyBytes, prettyNumber } from "@/utils/formatters";
+
+function Pair({
+  value,
+  format,
+  highlight,
+}: {
+  value: NetStatValue;
+  format: (num: number) => string;
+  highlight?: boolean;
+}) {
+  return (
+    <span
+      className={cn(
+        "tabular-nums",
+        highlight &&
+          (value.received || value.sent) &&
+          "font-bold text-destructive"
+      )}
+    >
+      {format(value.received)} / {format(value.sent)}
+    </span>
+  );
+}
+
+export const INTERFACE_COLUMNS: ColumnDef<NetworkInterface>[] = [
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Name
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "name",
+  },
+  {
+    header: "State",
+    accessorKey: "up",
+    cell: ({ row }) => (
+      <span className={cn(!row.original.up && "text-muted-foreground")}>
+        {row.original.up ? "Up" : "Down"}
+      </span>
+    ),
+  },
+  {
+    header: "MAC",
+    accessorKey: "mac",
+  },
+  {
+    header: "MTU",
+    accessorKey: "mtu",
+  },
+  {
+    header: "Addresses",
+    id: "addresses",
+    accessorFn: (row) => row.addresses.join(", "),
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Bytes (rx / tx)
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    id: "bytes",
+    accessorFn: (row) => row.bytes.received + row.bytes.sent,
+    cell: ({ row }) => <Pair value={row.original.bytes} format={prettyBytes} />,
+  },
+  {
+    header: "Packets (rx / tx)",
+    id: "packets",
+    cell: ({ row }) => (
+      <Pair value={row.original.packets} format={prettyNumber} />
+    ),
+  },
+  {
+    header: "Errors (rx / tx)",
+    id: "errors",
+    cell: ({ row }) => (
+      <Pair value={row.original.errors} format={prettyNumber} highlight />
+    ),
+  },
+  {
+    header: "Drops (rx / tx)",
+    id: "drops",
+    cell: ({ row }) => (
+      <Pair value={row.original.drops} format={prettyNumber} highlight />
+    ),
+  },
+];
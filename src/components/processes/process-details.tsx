@@ -0,0 +1,47 @@
+import { useProcessDetails } from "@/hooks/use-process-details";
+
+function Field({ label, value }: { label: string; value: string | null }) {
+  return (
+    <>
+      <div className="font-bold">{label}</div>
+      <div className="break-all font-mono text-xs text-muted-foreground">
+        {value ?? "-"}
+      </div>
+    </>
+  );
+}
+
+export function ProcessDetailsPanel({ pid }: { pid: string }) {
+  const { processDetails, error } = useProcessDetails(pid);
+
+  if (error) {
+    return <p className="text-sm text-destructive">{error}</p>;
+  }
+  if (!processDetails) {
+    return <p className="text-sm text-muted-foreground">Loading...</p>;
+  }
+
+  const { signature } = processDetails;
+
+  return (
+    <div className="grid grid-cols-[max-content_1fr] items-baseline gap-x-4 gap-y-1 text-sm">
+      <Field label="Executable" value={processDetails.executable_path} />
+      <Field label="Command line" value={processDetails.command_line} />
+      <Field
+        label="Working directory"
+        value={processDetails.working_directory}
+      />
+      <Field label="Owner" value={processDetails.owner} />
+      {signature ? (
+        <Field
+          label="Signature"
+          value={
+            signature.signer
+              ? `${signature.status} (${signature.signer})`
+              : signature.status
+          }
+        />
+      ) : null}
+    </div>
+  );
+}
@@ -0,0 +1,155 @@
+import { ChevronDown, ChevronRight } from "lucide-react";
+import { useState } from "react";
+
+import { Button } from "@/components/ui/button";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/components/ui/table";
+import { cn } from "@/lib/utils";
+import { ProcessNode } from "@/types";
+import { prettyBytes, prettyNumber } from "@/utils/formatters";
+
+interface ProcessTreeProps {
+  nodes: ProcessNode[];
+  selectedPid: string;
+  onSelect: (pid: string) => void;
+}
+
+interface ProcessTreeRowProps extends Omit<ProcessTreeProps, "nodes"> {
+  node: ProcessNode;
+  depth: number;
+  expanded: Set<string>;
+  onToggle: (pid: string) => void;
+}
+
+function ProcessTreeRow({
+  node,
+  depth,
+  expanded,
+  onToggle,
+  selectedPid,
+  onSelect,
+}: ProcessTreeRowProps) {
+  const pid = node.process.pid.toString();
+  const children = node.children.filter((child) => child.total_connections);
+  const isExpanded = expanded.has(pid);
+
+  // A collapsed row stands for the whole subtree
+  const connections = isExpanded ? node.connections : node.total_connections;
+  const sent = isExpanded ? node.bytes_sent : node.total_bytes_sent;
+  const received = isExpanded ? node.bytes_received : node.total_bytes_received;
+
+  return (
+    <>
+      <TableRow
+        className={cn("cursor-pointer", selectedPid === pid && "bg-muted")}
+        onClick={() => onSelect(pid)}
+      >
+        <TableCell>
+          <div
+            className="flex items-center"
+            style={{ paddingLeft: `${depth * 1.25}rem` }}
+          >
+            {children.length ? (
+              <Button
+                variant="ghost"
+                size="icon"
+                className="h-6 w-6"
+                onClick={(event) => {
+                  event.stopPropagation();
+                  onToggle(pid);
+                }}
+              >
+                {isExpanded ? (
+                  <ChevronDown className="h-4 w-4" />
+                ) : (
+                  <ChevronRight className="h-4 w-4" />
+                )}
+              </Button>
+            ) : (
+              <span className="w-6" />
+            )}
+            <span className="ml-1">{node.process.image_name}</span>
+            {!isExpanded && children.length ? (
+              <span className="ml-2 text-xs text-muted-foreground">
+                +{children.length}
+              </span>
+            ) : null}
+          </div>
+        </TableCell>
+        <TableCell className="tabular-nums">{pid}</TableCell>
+        <TableCell className="tabular-nums">
+          {prettyNumber(connections)}
+        </TableCell>
+        <TableCell className="tabular-nums">{prettyBytes(sent)}</TableCell>
+        <TableCell className="tabular-nums">{prettyBytes(received)}</TableCell>
+      </TableRow>
+
+      {isExpanded
+        ? children.map((child) => (
+            <ProcessTreeRow
+              key={child.process.pid}
+              node={child}
+              depth={depth + 1}
+              expanded={expanded}
+              onToggle={onToggle}
+              selectedPid={selectedPid}
+              onSelect={onSelect}
+            />
+          ))
+        : null}
+    </>
+  );
+}
+
+export function ProcessTree({ nodes, selectedPid, onSelect }: ProcessTreeProps) {
+  const [expanded, setExpanded] = useState<Set<string>>(new Set());
+
+  function onToggle(pid: string) {
+    setExpanded((prev) => {
+      const next = new Set(prev);
+      if (next.has(pid)) {
+        next.delete(pid);
+      } else {
+        next.add(pid);
+      }
+      return next;
+    });
+  }
+
+  return (
+    <div className="rounded-md border">
+      <Table>
+        <TableHeader>
+          <TableRow>
+            <TableHead>Process</TableHead>
+            <TableHead>PID</TableHead>
+            <TableHead>Connections</TableHead>
+            <TableHead>Sent</TableHead>
+            <TableHead>Received</TableHead>
+          </TableRow>
+        </TableHeader>
+        <TableBody>
+          {nodes
+            .filter((node) => node.total_connections)
+            .map((node) => (
+              <ProcessTreeRow
+                key={node.process.pid}
+                node={node}
+                depth={0}
+                expanded={expanded}
+                onToggle={onToggle}
+                selectedPid={selectedPid}
+                onSelect={onSelect}
+              />
+            ))}
+        </TableBody>
+      </Table>
+    </div>
+  );
+}
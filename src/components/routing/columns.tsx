@@ -0,0 +1,105 @@
+import { ColumnDef } from "@tanstack/react-table";
+import { ArrowUpDown } from "lucide-react";
+
+import { Button } from "@/components/ui/button";
+import { cn } from "@/lib/utils";
+import { Neighbor, Route } from "@/types";
+
+export const ROUTE_COLUMNS: ColumnDef<Route>[] = [
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Destination
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    id: "destination",
+    accessorFn: (row) => `${row.destination}/${row.prefix_len}`,
+  },
+  {
+    header: "Gateway",
+    id: "gateway",
+    accessorFn: (row) => row.gateway ?? "On-link",
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Interface
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "interface",
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Metric
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "metric",
+  },
+];
+
+export const NEIGHBOR_COLUMNS: ColumnDef<Neighbor>[] = [
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Address
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "address",
+  },
+  {
+    header: "MAC",
+    accessorKey: "mac",
+  },
+  {
+    header: ({ column }) => {
+      return (
+        <Button
+          variant="ghost"
+          onClick={() => column.toggleSorting(column.getIsSorted() === "asc")}
+        >
+          Interface
+          <ArrowUpDown className="ml-2 h-4 w-4" />
+        </Button>
+      );
+    },
+    accessorKey: "interface",
+  },
+  {
+    header: "State",
+    accessorKey: "state",
+    cell: ({ row }) => (
+      <span
+        className={cn(
+          row.original.state === "Incomplete" && "text-muted-foreground"
+        )}
+      >
+        {row.original.state}
+      </span>
+    ),
+  },
+];
@@ -0,0 +1,27 @@
+import { useRoute } from "@/hooks/use-route";
+
+export function ConnectionRoute({ address }: { address: string }) {
+  const { route, error } = useRoute(address);
+
+  let value: string;
+  if (error) {
+    value = error;
+  } else if (route === undefined) {
+    value = "Loading...";
+  } else if (route === null) {
+    value = "No matching route";
+  } else {
+    value = `${route.destination}/${route.prefix_len} ${
+      route.gateway ? `via ${route.gateway}` : "on-link"
+    } dev ${route.interface} metric ${route.metric}`;
+  }
+
+  return (
+    <div className="grid grid-cols-[max-content_1fr] items-baseline gap-x-4 gap-y-1 pt-2 text-sm">
+      <div className="font-bold">Route</div>
+      <div className="break-all font-mono text-xs text-muted-foreground">
+        {value}
+      </div>
+    </div>
+  );
+}
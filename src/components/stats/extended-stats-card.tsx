@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useState } from "react";
+
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { cn } from "@/lib/utils";
+import { ExtendedStats, ThresholdEvent } from "@/types";
+import { prettyNumber } from "@/utils/formatters";
+
+type Descriptions = Record<string, Record<string, string>>;
+
+interface ExtendedStatsCardProps {
+  stats: ExtendedStats;
+  alerts?: ThresholdEvent[];
+}
+
+/** The "advanced" card: every non-zero /proc/net/netstat counter. */
+export function ExtendedStatsCard({ stats, alerts }: ExtendedStatsCardProps) {
+  const [descriptions, setDescriptions] = useState<Descriptions>({});
+
+  useEffect(() => {
+    invoke<string>("extended_counter_descriptions")
+      .then((response) => setDescriptions(JSON.parse(response)))
+      .catch(() => setDescriptions({}));
+  }, []);
+
+  return (
+    <Card className={cn("min-w-max", alerts && "border-destructive")}>
+      <CardHeader>
+        <CardTitle>Advanced</CardTitle>
+        {alerts?.map((alert) => (
+          <p key={alert.name} className="text-xs font-medium text-destructive">
+            {alert.name}
+          </p>
+        ))}
+      </CardHeader>
+      <CardContent className="max-h-96 space-y-4 overflow-y-auto">
+        {Object.entries(stats).map(([section, counters]) => (
+          <div key={section}>
+            <div className="pb-1 text-sm font-semibold">{section}</div>
+            {Object.entries(counters)
+              .filter(([, value]) => value)
+              .map(([name, value]) => (
+                <div key={name} title={descriptions[section]?.[name]}>
+                  <div className="font-bold">{name}</div>
+                  <p className="text-xs text-muted-foreground">
+                    <span className="tabular-nums">{prettyNumber(value)}</span>
+                    {descriptions[section]?.[name] ? (
+                      <span> - {descriptions[section][name]}</span>
+                    ) : null}
+                  </p>
+                </div>
+              ))}
+          </div>
+        ))}
+      </CardContent>
+    </Card>
+  );
+}
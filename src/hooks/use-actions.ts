@@ -0,0 +1,28 @@
+import { invoke } from "@tauri-apps/api/tauri";
+
+import { toast } from "@/components/ui/use-toast";
+
+async function runAction(title: string, action: () => Promise<void>) {
+  try {
+    await action();
+    toast({ title });
+  } catch (error) {
+    toast({
+      variant: "destructive",
+      title: "Action failed",
+      description: String(error),
+    });
+  }
+}
+
+export function killProcess(pid: string) {
+  return runAction(`Process ${pid} terminated`, () =>
+    invoke("kill_process", { pid: Number(pid) })
+  );
+}
+
+export function closeConnection(localAddress: string, foreignAddress: string) {
+  return runAction(`Connection to ${foreignAddress} closed`, () =>
+    invoke("close_connection", { localAddress, foreignAddress })
+  );
+}
@@ -0,0 +1,42 @@
+import { ImportReport } from "@/types";
+import { open } from "@tauri-apps/api/dialog";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useState } from "react";
+
+/**
+ * Files of `netstat`, `ss`, `lsof` or `tasklist` output shown instead of the
+ * system's connections and processes, with their parse reports.
+ */
+export function useImport() {
+  const [reports, setReports] = useState<ImportReport[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<string>("imported_files")
+      .then((response) => setReports(JSON.parse(response)))
+      .catch(() => setReports([]));
+  }, []);
+
+  async function openFile() {
+    const path = await open({
+      filters: [{ name: "Command output", extensions: ["txt", "csv", "log"] }],
+    });
+    if (typeof path !== "string") return;
+
+    try {
+      const response: string = await invoke("open_file", { path });
+      setReports(JSON.parse(response));
+      setError(null);
+    } catch (error) {
+      setError(String(error));
+    }
+  }
+
+  async function closeFiles() {
+    await invoke("close_files");
+    setReports([]);
+    setError(null);
+  }
+
+  return { reports, error, openFile, closeFiles };
+}
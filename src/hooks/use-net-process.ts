@@ -1,9 +1,21 @@
-import { NetConnections, NetStat, Process } from "@/types";
+import {
+  ConnectionsByContainer,
+  Listener,
+  NetConnections,
+  Neighbor,
+  NetStatResult,
+  NetworkInterface,
+  Process,
+  ProcessNode,
+  Route,
+} from "@/types";
 import { invoke } from "@tauri-apps/api/tauri";
 import { useEffect, useState } from "react";
 
 function useNetStat(enabled: boolean) {
-  const [netStatResult, setNetStatResult] = useState<NetStat | null>(null);
+  const [netStatResult, setNetStatResult] = useState<NetStatResult | null>(
+    null
+  );
 
   async function net_stat() {
     const response: string = await invoke("net_stat");
@@ -31,14 +43,43 @@ function useNetStat(enabled: boolean) {
   return { netStatResult, net_stat };
 }
 
+function useNetInterfaces(enabled: boolean) {
+  const [netInterfacesResult, setNetInterfacesResult] = useState<
+    NetworkInterface[]
+  >([]);
+
+  async function net_interfaces() {
+    try {
+      const response: string = await invoke("net_interfaces");
+      setNetInterfacesResult(JSON.parse(response));
+    } catch {
+      setNetInterfacesResult([]);
+    }
+  }
+
+  useEffect(() => {
+    let interval: NodeJS.Timeout | null = null;
+    if (enabled) {
+      interval = setInterval(() => {
+        net_interfaces();
+      }, 2500);
+    }
+
+    return () => {
+      interval && clearInterval(interval);
+    };
+  }, [enabled]);
+
+  return { netInterfacesResult, net_interfaces };
+}
+
 function useNetConnections(enabled: boolean) {
   const [netConnectionsResult, setNetConnectionsResult] =
     useState<NetConnections>({});
 
   async function net_connections() {
-    const response: string = await invoke("net_connections");
-
     try {
+      const response: string = await invoke("net_connections");
       setNetConnectionsResult(JSON.parse(response));
     } catch {
       setNetConnectionsResult({});
@@ -61,6 +102,35 @@ function useNetConnections(enabled: boolean) {
   return { netConnectionsResult, net_connections };
 }
 
+function useConnectionsByContainer(enabled: boolean) {
+  const [connectionsByContainerResult, setConnectionsByContainerResult] =
+    useState<ConnectionsByContainer>({});
+
+  async function net_connections_by_container() {
+    try {
+      const response: string = await invoke("net_connections_by_container");
+      setConnectionsByContainerResult(JSON.parse(response));
+    } catch {
+      setConnectionsByContainerResult({});
+    }
+  }
+
+  useEffect(() => {
+    let interval: NodeJS.Timeout | null = null;
+    if (enabled) {
+      interval = setInterval(() => {
+        net_connections_by_container();
+      }, 2500);
+    }
+
+    return () => {
+      interval && clearInterval(interval);
+    };
+  }, [enabled]);
+
+  return { connectionsByContainerResult, net_connections_by_container };
+}
+
 function useProcesses(enabled: boolean) {
   const [processesResult, setProcessesResult] = useState<Process[]>([]);
 
@@ -91,21 +161,139 @@ function useProcesses(enabled: boolean) {
   return { processesResult, processes };
 }
 
+function useProcessTree(enabled: boolean) {
+  const [processTreeResult, setProcessTreeResult] = useState<ProcessNode[]>(
+    []
+  );
+
+  async function process_tree() {
+    try {
+      const response: string = await invoke("process_tree");
+      setProcessTreeResult(JSON.parse(response));
+    } catch {
+      setProcessTreeResult([]);
+    }
+  }
+
+  useEffect(() => {
+    let interval: NodeJS.Timeout | null = null;
+    if (enabled) {
+      interval = setInterval(() => {
+        process_tree();
+      }, 2500);
+    }
+
+    return () => {
+      interval && clearInterval(interval);
+    };
+  }, [enabled]);
+
+  return { processTreeResult, process_tree };
+}
+
+function useListeningPorts(enabled: boolean) {
+  const [listeningPortsResult, setListeningPortsResult] = useState<
+    Listener[]
+  >([]);
+
+  async function listening_ports() {
+    try {
+      const response: string = await invoke("listening_ports");
+      setListeningPortsResult(JSON.parse(response));
+    } catch {
+      setListeningPortsResult([]);
+    }
+  }
+
+  useEffect(() => {
+    let interval: NodeJS.Timeout | null = null;
+    if (enabled) {
+      interval = setInterval(() => {
+        listening_ports();
+      }, 2500);
+    }
+
+    return () => {
+      interval && clearInterval(interval);
+    };
+  }, [enabled]);
+
+  return { listeningPortsResult, listening_ports };
+}
+
+function useRoutes(enabled: boolean) {
+  const [routesResult, setRoutesResult] = useState<Route[]>([]);
+  const [neighborsResult, setNeighborsResult] = useState<Neighbor[]>([]);
+
+  async function routes() {
+    try {
+      const response: string = await invoke("routes");
+      setRoutesResult(JSON.parse(response));
+    } catch {
+      setRoutesResult([]);
+    }
+  }
+
+  async function neighbors() {
+    try {
+      const response: string = await invoke("neighbors");
+      setNeighborsResult(JSON.parse(response));
+    } catch {
+      setNeighborsResult([]);
+    }
+  }
+
+  useEffect(() => {
+    let interval: NodeJS.Timeout | null = null;
+    if (enabled) {
+      interval = setInterval(() => {
+        routes();
+        neighbors();
+      }, 5000);
+    }
+
+    return () => {
+      interval && clearInterval(interval);
+    };
+  }, [enabled]);
+
+  return { routesResult, neighborsResult, routes, neighbors };
+}
+
 export function useNetProcess(enabled: boolean) {
   const { netStatResult, net_stat } = useNetStat(enabled);
+  const { netInterfacesResult, net_interfaces } = useNetInterfaces(enabled);
   const { netConnectionsResult, net_connections } = useNetConnections(enabled);
+  const { connectionsByContainerResult, net_connections_by_container } =
+    useConnectionsByContainer(enabled);
   const { processesResult, processes } = useProcesses(enabled);
+  const { processTreeResult, process_tree } = useProcessTree(enabled);
+  const { listeningPortsResult, listening_ports } = useListeningPorts(enabled);
+  const { routesResult, neighborsResult, routes, neighbors } =
+    useRoutes(enabled);
 
   // Initial load
   useEffect(() => {
     net_stat();
+    net_interfaces();
     net_connections();
+    net_connections_by_container();
     processes();
+    process_tree();
+    listening_ports();
+    routes();
+    neighbors();
   }, []);
 
   return {
     netStatResult,
+    netInterfacesResult,
     netConnectionsResult,
+    connectionsByContainerResult,
     processesResult,
+    processTreeResult,
+    listeningPortsResult,
+    routesResult,
+    neighborsResult,
   };
 }
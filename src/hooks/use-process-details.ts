@@ -0,0 +1,33 @@
+import { ProcessDetails } from "@/types";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useState } from "react";
+
+export function useProcessDetails(pid: string) {
+  const [processDetails, setProcessDetails] = useState<ProcessDetails | null>(
+    null
+  );
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+
+    async function process_details() {
+      try {
+        const response: string = await invoke("process_details", { pid });
+        if (!cancelled) setProcessDetails(JSON.parse(response));
+      } catch (error) {
+        if (!cancelled) setError(String(error));
+      }
+    }
+
+    setProcessDetails(null);
+    setError(null);
+    process_details();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [pid]);
+
+  return { processDetails, error };
+}
@@ -0,0 +1,34 @@
+import { GroupBy, ProcessGroup } from "@/types";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useState } from "react";
+
+/** Processes and their connections by user or session, most connections first. */
+export function useProcessGroups(enabled: boolean, by: GroupBy) {
+  const [processGroups, setProcessGroups] = useState<ProcessGroup[]>([]);
+
+  useEffect(() => {
+    async function process_groups() {
+      try {
+        const response: string = await invoke("process_groups", { by });
+        setProcessGroups(JSON.parse(response));
+      } catch {
+        setProcessGroups([]);
+      }
+    }
+
+    process_groups();
+
+    let interval: NodeJS.Timeout | null = null;
+    if (enabled) {
+      interval = setInterval(() => {
+        process_groups();
+      }, 2500);
+    }
+
+    return () => {
+      interval && clearInterval(interval);
+    };
+  }, [enabled, by]);
+
+  return { processGroups };
+}
@@ -0,0 +1,34 @@
+import { Route } from "@/types";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useState } from "react";
+
+/** The route used to reach `address`, `null` when none matches. */
+export function useRoute(address: string) {
+  const [route, setRoute] = useState<Route | null | undefined>(undefined);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+
+    async function route_for_address() {
+      try {
+        const response: string = await invoke("route_for_address", {
+          address,
+        });
+        if (!cancelled) setRoute(JSON.parse(response));
+      } catch (error) {
+        if (!cancelled) setError(String(error));
+      }
+    }
+
+    setRoute(undefined);
+    setError(null);
+    route_for_address();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [address]);
+
+  return { route, error };
+}
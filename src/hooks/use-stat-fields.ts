@@ -0,0 +1,16 @@
+import { StatFields } from "@/types";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useState } from "react";
+
+/** Units and descriptions of the known net stat counters, fetched once. */
+export function useStatFields() {
+  const [statFields, setStatFields] = useState<StatFields>({});
+
+  useEffect(() => {
+    invoke<string>("stat_fields")
+      .then((response) => setStatFields(JSON.parse(response)))
+      .catch(() => setStatFields({}));
+  }, []);
+
+  return { statFields };
+}
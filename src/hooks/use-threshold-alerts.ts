@@ -0,0 +1,39 @@
+import { NetStatResult, ThresholdEvent } from "@/types";
+import { listen } from "@tauri-apps/api/event";
+import { useEffect, useMemo, useState } from "react";
+
+/** Thresholds currently crossed, keyed by name. */
+export function useThresholdAlerts() {
+  const [crossed, setCrossed] = useState<Record<string, ThresholdEvent>>({});
+
+  useEffect(() => {
+    const unlisten = listen<ThresholdEvent>("threshold-alert", (event) => {
+      const alert = event.payload;
+
+      setCrossed((prev) => {
+        const next = { ...prev };
+        if (alert.crossed) {
+          next[alert.name] = alert;
+        } else {
+          delete next[alert.name];
+        }
+        return next;
+      });
+    });
+
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // Cards to highlight, with the thresholds that hit them
+  const alertsByCard = useMemo(() => {
+    const cards: Partial<Record<keyof NetStatResult, ThresholdEvent[]>> = {};
+    for (const alert of Object.values(crossed)) {
+      (cards[alert.card] ??= []).push(alert);
+    }
+    return cards;
+  }, [crossed]);
+
+  return { alertsByCard };
+}
@@ -0,0 +1,229 @@
+// Generated from the `stats_struct!` definitions in src-tauri/src/lib.rs
+// by `typescript_definitions`, do not edit.
+
+export type NetStatValue = {
+  received: number;
+  sent: number;
+};
+
+export type NetStat = {
+  interface: {
+    /** Bytes received and sent on all adapters. */
+    bytes: NetStatValue;
+    /** Packets addressed to a single host. */
+    unicast_packets: NetStatValue;
+    /** Broadcast and multicast packets. */
+    non_unicast_packets: NetStatValue;
+    /** Packets dropped without an error, e.g. for lack of buffer space. */
+    discards: NetStatValue;
+    /** Packets dropped because they were malformed. */
+    errors: NetStatValue;
+    /** Packets for a protocol the host does not speak. */
+    unknown_protocols: number;
+    [counter: string]: NetStatValue | number;
+  };
+  ipv4: {
+    /** Datagrams received, including the ones in error. */
+    packets_received: number;
+    /** Datagrams dropped for a bad header (checksum, version, TTL, ...). */
+    received_header_errors: number;
+    /** Datagrams dropped because the destination is not a local address. */
+    received_address_errors: number;
+    /** Datagrams routed on to another host. */
+    datagrams_forwarded: number;
+    /** Datagrams for a transport protocol the host does not speak. */
+    unknown_protocols_received: number;
+    /** Valid datagrams dropped, e.g. for lack of buffer space. */
+    received_packets_discarded: number;
+    /** Datagrams handed to a transport protocol. */
+    received_packets_delivered: number;
+    /** Datagrams the host asked to send, forwarded ones excluded. */
+    output_requests: number;
+    /** Routing entries dropped although they were valid. */
+    routing_discards: number;
+    /** Valid outgoing datagrams dropped, e.g. for lack of buffer space. */
+    discarded_output_packets: number;
+    /** Outgoing datagrams dropped because no route matched. */
+    output_packet_no_route: number;
+    /** Fragments received that needed reassembly. */
+    reassembly_required: number;
+    /** Datagrams reassembled from fragments. */
+    reassembly_successful: number;
+    /** Reassemblies that failed, e.g. on a timeout. */
+    reassembly_failures: number;
+    /** Datagrams split into fragments. */
+    datagrams_successfully_fragmented: number;
+    /** Datagrams dropped because they needed fragmenting but had Don't Fragment set. */
+    datagrams_failing_fragmentation: number;
+    /** Fragments generated by fragmentation. */
+    fragments_created: number;
+    [counter: string]: NetStatValue | number;
+  };
+  ipv6: {
+    /** Datagrams received, including the ones in error. */
+    packets_received: number;
+    /** Datagrams dropped for a bad header (version, hop limit, options, ...). */
+    received_header_errors: number;
+    /** Datagrams dropped because the destination is not a local address. */
+    received_address_errors: number;
+    /** Datagrams routed on to another host. */
+    datagrams_forwarded: number;
+    /** Datagrams for a transport protocol the host does not speak. */
+    unknown_protocols_received: number;
+    /** Valid datagrams dropped, e.g. for lack of buffer space. */
+    received_packets_discarded: number;
+    /** Datagrams handed to a transport protocol. */
+    received_packets_delivered: number;
+    /** Datagrams the host asked to send, forwarded ones excluded. */
+    output_requests: number;
+    /** Routing entries dropped although they were valid. */
+    routing_discards: number;
+    /** Valid outgoing datagrams dropped, e.g. for lack of buffer space. */
+    discarded_output_packets: number;
+    /** Outgoing datagrams dropped because no route matched. */
+    output_packet_no_route: number;
+    /** Fragments received that needed reassembly. */
+    reassembly_required: number;
+    /** Datagrams reassembled from fragments. */
+    reassembly_successful: number;
+    /** Reassemblies that failed, e.g. on a timeout. */
+    reassembly_failures: number;
+    /** Datagrams split into fragments. */
+    datagrams_successfully_fragmented: number;
+    /** Datagrams that needed fragmenting but could not be. */
+    datagrams_failing_fragmentation: number;
+    /** Fragments generated by fragmentation. */
+    fragments_created: number;
+    [counter: string]: NetStatValue | number;
+  };
+  icmpv4: {
+    /** All ICMP messages, including the ones in error. */
+    messages: NetStatValue;
+    /** Messages with ICMP specific errors such as a bad checksum. */
+    errors: NetStatValue;
+    /** Host, network, port or protocol unreachable. */
+    destination_unreachable: NetStatValue;
+    /** TTL or reassembly time exceeded, as seen by traceroute. */
+    time_exceeded: NetStatValue;
+    /** Datagrams with a header problem. */
+    parameter_problems: NetStatValue;
+    /** Requests to slow down, obsolete. */
+    source_quenches: NetStatValue;
+    /** Better routes announced by a router. */
+    redirects: NetStatValue;
+    /** Ping replies. */
+    echo_replies: NetStatValue;
+    /** Ping requests. */
+    echos: NetStatValue;
+    /** Timestamp requests. */
+    timestamps: NetStatValue;
+    /** Timestamp replies. */
+    timestamp_replies: NetStatValue;
+    /** Address mask requests. */
+    address_masks: NetStatValue;
+    /** Address mask replies. */
+    address_mask_replies: NetStatValue;
+    /** Hosts looking for a router. */
+    router_solicitations: NetStatValue;
+    /** Routers announcing themselves. */
+    router_advertisements: NetStatValue;
+    [counter: string]: NetStatValue | number;
+  };
+  icmpv6: {
+    /** All ICMPv6 messages, including the ones in error. */
+    messages: NetStatValue;
+    /** Messages with ICMPv6 specific errors such as a bad checksum. */
+    errors: NetStatValue;
+    /** Address, port or route unreachable. */
+    destination_unreachable: NetStatValue;
+    /** A packet exceeded the path MTU, used for path MTU discovery. */
+    packet_too_big: NetStatValue;
+    /** Hop limit or reassembly time exceeded, as seen by traceroute. */
+    time_exceeded: NetStatValue;
+    /** Datagrams with a header problem. */
+    parameter_problems: NetStatValue;
+    /** Ping requests. */
+    echos: NetStatValue;
+    /** Ping replies. */
+    echo_replies: NetStatValue;
+    /** Multicast listener queries. */
+    mld_queries: NetStatValue;
+    /** Multicast listener reports. */
+    mld_reports: NetStatValue;
+    /** Multicast listeners leaving a group. */
+    mld_dones: NetStatValue;
+    /** Hosts looking for a router. */
+    router_solicitations: NetStatValue;
+    /** Routers announcing themselves and their prefixes. */
+    router_advertisements: NetStatValue;
+    /** Neighbor discovery requests, the IPv6 ARP. */
+    neighbor_solicitations: NetStatValue;
+    /** Neighbor discovery replies. */
+    neighbor_advertisements: NetStatValue;
+    /** Better routes announced by a router. */
+    redirects: NetStatValue;
+    /** Router renumbering messages. */
+    router_renumberings: NetStatValue;
+    [counter: string]: NetStatValue | number;
+  };
+  tcp_ipv4: {
+    /** Connections opened by this host (SYN-SENT). */
+    active_opens: number;
+    /** Connections accepted from other hosts (SYN-RCVD). */
+    passive_opens: number;
+    /** Connections that failed before being established. */
+    failed_connection_attempts: number;
+    /** Established connections that were reset. */
+    reset_connections: number;
+    /** Connections currently established or closing. */
+    current_connections: number;
+    /** Segments received, including the ones in error. */
+    segments_received: number;
+    /** Segments sent, retransmissions excluded. */
+    segments_sent: number;
+    /** Segments sent again because they were not acknowledged. */
+    segments_retransmitted: number;
+    [counter: string]: NetStatValue | number;
+  };
+  tcp_ipv6: {
+    /** Connections opened by this host (SYN-SENT). */
+    active_opens: number;
+    /** Connections accepted from other hosts (SYN-RCVD). */
+    passive_opens: number;
+    /** Connections that failed before being established. */
+    failed_connection_attempts: number;
+    /** Established connections that were reset. */
+    reset_connections: number;
+    /** Connections currently established or closing. */
+    current_connections: number;
+    /** Segments received, including the ones in error. */
+    segments_received: number;
+    /** Segments sent, retransmissions excluded. */
+    segments_sent: number;
+    /** Segments sent again because they were not acknowledged. */
+    segments_retransmitted: number;
+    [counter: string]: NetStatValue | number;
+  };
+  udp_ipv4: {
+    /** Datagrams handed to an application. */
+    datagrams_received: number;
+    /** Datagrams for a port nothing listens on. */
+    no_ports: number;
+    /** Datagrams dropped for other reasons, e.g. a full receive buffer. */
+    receive_errors: number;
+    /** Datagrams sent. */
+    datagrams_sent: number;
+    [counter: string]: NetStatValue | number;
+  };
+  udp_ipv6: {
+    /** Datagrams handed to an application. */
+    datagrams_received: number;
+    /** Datagrams for a port nothing listens on. */
+    no_ports: number;
+    /** Datagrams dropped for other reasons, e.g. a full receive buffer. */
+    receive_errors: number;
+    /** Datagrams sent. */
+    datagrams_sent: number;
+    [counter: string]: NetStatValue | number;
+  };
+};
@@ -1,141 +1,198 @@
-export type NetStatValue = {
-  received: number;
-  sent: number;
-};
-
-export type NetStat = {
-  interface: {
-    bytes: NetStatValue;
-    unicast_packets: NetStatValue;
-    non_unicast_packets: NetStatValue;
-    discards: NetStatValue;
-    errors: NetStatValue;
-    unknown_protocols: number;
-  };
-  ipv4: {
-    packets_received: number;
-    received_header_errors: number;
-    received_address_errors: number;
-    datagrams_forwarded: number;
-    unknown_protocols_received: number;
-    received_packets_discarded: number;
-    received_packets_delivered: number;
-    output_requests: number;
-    routing_discards: number;
-    discarded_output_packets: number;
-    output_packet_no_route: number;
-    reassembly_required: number;
-    reassembly_successful: number;
-    reassembly_failures: number;
-    datagrams_successfully_fragmented: number;
-    datagrams_failing_fragmentation: number;
-    fragments_created: number;
-  };
-  ipv6: {
-    packets_received: number;
-    received_header_errors: number;
-    received_address_errors: number;
-    datagrams_forwarded: number;
-    unknown_protocols_received: number;
-    received_packets_discarded: number;
-    received_packets_delivered: number;
-    output_requests: number;
-    routing_discards: number;
-    discarded_output_packets: number;
-    output_packet_no_route: number;
-    reassembly_required: number;
-    reassembly_successful: number;
-    reassembly_failures: number;
-    datagrams_successfully_fragmented: number;
-    datagrams_failing_fragmentation: number;
-    fragments_created: number;
-  };
-  icmpv4: {
-    messages: NetStatValue;
-    errors: NetStatValue;
-    destination_unreachable: NetStatValue;
-    time_exceeded: NetStatValue;
-    parameter_problems: NetStatValue;
-    source_quenches: NetStatValue;
-    redirects: NetStatValue;
-    echo_replies: NetStatValue;
-    echos: NetStatValue;
-    timestamps: NetStatValue;
-    timestamp_replies: NetStatValue;
-    address_masks: NetStatValue;
-    address_mask_replies: NetStatValue;
-    router_solicitations: NetStatValue;
-    router_advertisements: NetStatValue;
-  };
-  icmpv6: {
-    messages: NetStatValue;
-    errors: NetStatValue;
-    destination_unreachable: NetStatValue;
-    packet_too_big: NetStatValue;
-    time_exceeded: NetStatValue;
-    parameter_problems: NetStatValue;
-    echos: NetStatValue;
-    echo_replies: NetStatValue;
-    mld_queries: NetStatValue;
-    mld_reports: NetStatValue;
-    mld_dones: NetStatValue;
-    router_solicitations: NetStatValue;
-    router_advertisements: NetStatValue;
-    neighbor_solicitations: NetStatValue;
-    neighbor_advertisements: NetStatValue;
-    redirects: NetStatValue;
-    router_renumberings: NetStatValue;
-  };
-  tcp_ipv4: {
-    active_opens: number;
-    passive_opens: number;
-    failed_connection_attempts: number;
-    reset_connections: number;
-    current_connections: number;
-    segments_received: number;
-    segments_sent: number;
-    segments_retransmitted: number;
-  };
-  tcp_ipv6: {
-    active_opens: number;
-    passive_opens: number;
-    failed_connection_attempts: number;
-    reset_connections: number;
-    current_connections: number;
-    segments_received: number;
-    segments_sent: number;
-    segments_retransmitted: number;
-  };
-  udp_ipv4: {
-    datagrams_received: number;
-    no_ports: number;
-    receive_errors: number;
-    datagrams_sent: number;
-  };
-  udp_ipv6: {
-    datagrams_received: number;
-    no_ports: number;
-    receive_errors: number;
-    datagrams_sent: number;
-  };
+import type { NetStat } from "./net-stat";
+
+export type { NetStat, NetStatValue } from "./net-stat";
+
+/** How a `NetStat` counter is defined in the backend. */
+export type StatField = {
+  name: string;
+  label: string;
+  snmp: string[];
+  unit: string;
+  description: string;
+  pair: boolean;
 };
 
+export type StatFields = Record<string, Record<string, StatField>>;
+
+/** Linux `/proc/net/netstat` counters, by section and name. */
+export type ExtendedStats = Record<string, Record<string, number>>;
+
+// Besides these, any section or counter the backend finds without knowing
+// it in advance (e.g. `sctp_ipv4`).
+export type NetStatResult = NetStat & { extended?: ExtendedStats };
+
 export type NetStatKeys = keyof NetStat;
 export type NetStatChildKeys = keyof NetStat[NetStatKeys];
 
+export type TcpInfo = {
+  rtt_us: number;
+  rtt_var_us: number;
+  snd_cwnd: number;
+  retransmits: number;
+  bytes_acked: number;
+  bytes_received: number;
+  uid: number;
+};
+
+export type SocketTimer = {
+  name: string;
+  expires_ms: number;
+  retransmits: number;
+};
+
+export type GeoInfo = {
+  country_code: string | null;
+  country: string | null;
+  asn: number | null;
+  organization: string | null;
+};
+
+export type AddressClass =
+  | "Unspecified"
+  | "Loopback"
+  | "Private"
+  | "Cgnat"
+  | "LinkLocal"
+  | "UniqueLocal"
+  | "Multicast"
+  | "Broadcast"
+  | "Public";
+
 export type Connection = {
   connection_type: string;
   local_address: string;
   foreign_address: string;
   state: string;
   pid: string;
+  send_queue: number | null;
+  recv_queue: number | null;
+  tcp_info: TcpInfo | null;
+  process_name: string | null;
+  uid: number | null;
+  inode: number | null;
+  timer: SocketTimer | null;
+  net_namespace: number | null;
+  cgroup: string | null;
+  container_id: string | null;
+  foreign_hostname: string | null;
+  service: string | null;
+  ephemeral_local_port: boolean;
+  geo: GeoInfo | null;
+  local_class: AddressClass | null;
+  foreign_class: AddressClass | null;
+  local_mapped: boolean;
+  foreign_mapped: boolean;
 };
 
 export type NetConnections = Record<string, Connection[]>;
 
+/** Connections by container ID, `host` for those outside of any. */
+export type ConnectionsByContainer = Record<string, Connection[]>;
+
 export type Process = {
   image_name: string;
   pid: number;
   session_name: string;
   session_number: string;
+  parent_pid: string | null;
+  user: string | null;
+  net_namespace: number | null;
+  cgroup: string | null;
+  container_id: string | null;
+};
+
+export type Signature = {
+  status: string;
+  signer: string | null;
+};
+
+export type ProcessDetails = {
+  pid: string;
+  executable_path: string | null;
+  command_line: string | null;
+  working_directory: string | null;
+  owner: string | null;
+  signature: Signature | null;
+};
+
+export type ProcessNode = {
+  process: Process;
+  connections: number;
+  bytes_sent: number;
+  bytes_received: number;
+  total_connections: number;
+  total_bytes_sent: number;
+  total_bytes_received: number;
+  children: ProcessNode[];
+};
+
+export type BindScope = "AllInterfaces" | "LoopbackOnly" | "Specific";
+
+export type Listener = {
+  connection_type: string;
+  port: number;
+  scope: BindScope;
+  addresses: string[];
+  ipv4: boolean;
+  ipv6: boolean;
+  pid: string;
+  image_name: string | null;
+  service: string | null;
+};
+
+export type ThresholdEvent = {
+  name: string;
+  counter: string;
+  per: string | null;
+  card: keyof NetStatResult;
+  value: number;
+  above: number;
+  crossed: boolean;
+  timestamp: number;
+};
+
+export type NetworkInterface = {
+  name: string;
+  mac: string | null;
+  mtu: number | null;
+  up: boolean;
+  addresses: string[];
+  bytes: NetStatValue;
+  packets: NetStatValue;
+  errors: NetStatValue;
+  drops: NetStatValue;
+};
+
+export type Route = {
+  destination: string;
+  prefix_len: number;
+  gateway: string | null;
+  interface: string;
+  metric: number;
+};
+
+export type NeighborState = "Dynamic" | "Static" | "Incomplete";
+
+export type Neighbor = {
+  address: string;
+  mac: string | null;
+  interface: string;
+  state: NeighborState;
+};
+
+export type ImportFormat = "netstat" | "ss" | "lsof" | "tasklist";
+
+export type ImportReport = {
+  name: string;
+  format: ImportFormat;
+  parsed: number;
+  skipped: { line: number; text: string }[];
+};
+
+export type GroupBy = "user" | "session";
+
+export type ProcessGroup = {
+  key: string;
+  processes: Process[];
+  connections: Connection[];
 };